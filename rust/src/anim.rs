@@ -0,0 +1,224 @@
+//! Terminal animation for the days simulating a grid over time.
+//!
+//! Simulations emit their frames with [`frame()`], which does nothing unless the binary is
+//! started with `--animate`. The animation can be tuned with the following options:
+//! - `--frame-delay <ms>`: Pause between two frames (Default: 50).
+//! - `--every <n>`: Render only each n-th frame for long simulations.
+//! - `--step`: Wait for Enter after each frame. Typing `c` continues without waiting and `q`
+//!   stops the animation.
+//! - `--viewport <row>,<col>,<height>,<width>`: Crop the frames to the given window.
+
+use std::{
+    env,
+    fmt::Display,
+    io::{self, Write},
+    sync::{Mutex, OnceLock},
+    thread,
+    time::Duration,
+};
+
+use crate::utls::{find_arg_value, has_flag};
+
+const DEFAULT_DELAY_MS: u64 = 50;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Viewport {
+    row: usize,
+    col: usize,
+    height: usize,
+    width: usize,
+}
+
+impl Viewport {
+    fn parse(text: &str) -> Option<Self> {
+        let nums: Vec<usize> = text
+            .split(',')
+            .map(|num| num.trim().parse().ok())
+            .collect::<Option<_>>()?;
+        let &[row, col, height, width] = nums.as_slice() else {
+            return None;
+        };
+
+        Some(Self {
+            row,
+            col,
+            height,
+            width,
+        })
+    }
+
+    fn crop(&self, frame: &str) -> String {
+        frame
+            .lines()
+            .skip(self.row)
+            .take(self.height)
+            .map(|line| {
+                let mut cropped: String = line.chars().skip(self.col).take(self.width).collect();
+                cropped.push('\n');
+                cropped
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Options {
+    delay: Duration,
+    every: usize,
+    step: bool,
+    viewport: Option<Viewport>,
+}
+
+impl Options {
+    /// Parses the animation options from the command line arguments, ignoring all the others.
+    /// Returns None if the animation isn't requested.
+    fn parse(args: impl IntoIterator<Item = String>) -> Option<Self> {
+        let args: Vec<String> = args.into_iter().collect();
+        if !has_flag(args.iter().cloned(), "--animate") {
+            return None;
+        }
+
+        let delay = find_arg_value(args.iter().cloned(), "--frame-delay")
+            .map(|ms| ms.parse().expect("--frame-delay needs ms"))
+            .unwrap_or(DEFAULT_DELAY_MS);
+        let every = find_arg_value(args.iter().cloned(), "--every")
+            .map(|n| {
+                n.parse()
+                    .ok()
+                    .filter(|n| *n > 0)
+                    .expect("--every needs n > 0")
+            })
+            .unwrap_or(1);
+        let viewport = find_arg_value(args.iter().cloned(), "--viewport").map(|text| {
+            Viewport::parse(&text).expect("--viewport needs <row>,<col>,<height>,<width>")
+        });
+
+        Some(Options {
+            delay: Duration::from_millis(delay),
+            every,
+            step: has_flag(args.iter().cloned(), "--step"),
+            viewport,
+        })
+    }
+}
+
+#[derive(Debug)]
+struct Animator {
+    options: Options,
+    frames_count: usize,
+    stopped: bool,
+}
+
+impl Animator {
+    fn new(options: Options) -> Self {
+        Self {
+            options,
+            frames_count: 0,
+            stopped: false,
+        }
+    }
+
+    fn should_show(&mut self) -> bool {
+        self.frames_count += 1;
+        !self.stopped && (self.frames_count - 1).is_multiple_of(self.options.every)
+    }
+
+    fn show(&mut self, title: impl Display, frame: &str) {
+        let frame = match &self.options.viewport {
+            Some(viewport) => viewport.crop(frame),
+            None => frame.to_owned(),
+        };
+
+        let mut stdout = io::stdout().lock();
+        // Clear the screen and move the cursor to the top left corner.
+        write!(stdout, "\x1b[2J\x1b[H{title}\n{frame}").unwrap();
+        stdout.flush().unwrap();
+        drop(stdout);
+
+        if self.options.step {
+            print!("[Enter] next, [c] continue, [q] quit animation: ");
+            io::stdout().flush().unwrap();
+            let mut answer = String::new();
+            io::stdin().read_line(&mut answer).unwrap();
+            match answer.trim() {
+                "c" => self.options.step = false,
+                "q" => self.stopped = true,
+                _ => {}
+            }
+        } else {
+            thread::sleep(self.options.delay);
+        }
+    }
+}
+
+static ANIMATOR: OnceLock<Option<Mutex<Animator>>> = OnceLock::new();
+
+fn animator() -> Option<&'static Mutex<Animator>> {
+    ANIMATOR
+        .get_or_init(|| {
+            Options::parse(env::args().skip(1)).map(|opts| Mutex::new(Animator::new(opts)))
+        })
+        .as_ref()
+}
+
+/// Shows the frame returned by the render function when the animation is enabled.
+/// The frame is only rendered when it will be shown.
+pub fn frame(title: impl Display, render: impl FnOnce() -> String) {
+    let Some(animator) = animator() else {
+        return;
+    };
+
+    let mut animator = animator.lock().unwrap();
+    if animator.should_show() {
+        animator.show(title, &render());
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn parse(args: &str) -> Option<Options> {
+        Options::parse(args.split_whitespace().map(String::from))
+    }
+
+    #[test]
+    fn test_options() {
+        assert_eq!(parse("--step --frame-delay 10"), None);
+
+        let options = parse("--nocapture --animate --frame-delay 10 --every 3").unwrap();
+        assert_eq!(options.delay, Duration::from_millis(10));
+        assert_eq!(options.every, 3);
+        assert!(!options.step);
+        assert_eq!(options.viewport, None);
+
+        let options = parse("--animate --step --viewport 1,2,2,3").unwrap();
+        assert!(options.step);
+        assert_eq!(
+            options.viewport,
+            Some(Viewport {
+                row: 1,
+                col: 2,
+                height: 2,
+                width: 3
+            })
+        );
+    }
+
+    #[test]
+    fn test_crop() {
+        let viewport = Viewport::parse("1,2,2,3").unwrap();
+        assert_eq!(
+            viewport.crop("abcdef\nghijkl\nmnopqr\nstuvwx"),
+            "ijk\nopq\n"
+        );
+        assert_eq!(Viewport::parse("1,2,3"), None);
+    }
+
+    #[test]
+    fn test_every() {
+        let mut animator = Animator::new(parse("--animate --every 3").unwrap());
+        let shown: Vec<_> = (0..7).map(|_| animator.should_show()).collect();
+        assert_eq!(shown, [true, false, false, true, false, false, true]);
+    }
+}
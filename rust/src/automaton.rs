@@ -0,0 +1,493 @@
+//! Cellular automaton engine for the puzzles with Life-like rules.
+//!
+//! An [`Automaton`] keeps the cell states in a [`Storage`] and updates all of them at once in
+//! each step, computing the next state of a cell from its current state and the states of its
+//! neighbours picked by the [`Neighbourhood`]. On top of the plain rule it can:
+//! - Treat the grid as infinite. All the cells outside the storage share the background state,
+//!   which follows the rule like any other cell, and dense storage grows by one cell on each side
+//!   per step so the cells near the edge can change.
+//! - Keep fixed cells in their states regardless of the rule.
+//! - Run until a step changes nothing.
+//!
+//! Days where a cell can't be updated from its neighbourhood alone, like the cascading octopus
+//! flashes or the elves resolving their proposed moves together, keep their own step and share
+//! only the [`Storage`] types and [`ADJACENT`].
+
+use std::collections::HashMap;
+
+use rustc_hash::{FxHashMap, FxHashSet};
+
+/// Row and column of a cell.
+pub type Pos = (isize, isize);
+
+/// The offsets of the eight adjacent cells in reading order.
+pub const ADJACENT: [Pos; 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+
+#[derive(Debug, Clone, Copy)]
+pub enum Neighbourhood<S> {
+    /// The eight adjacent cells in reading order.
+    Adjacent,
+    /// The first cell in each adjacent direction that isn't see-through according to the given
+    /// function, or the background if there is none inside the storage.
+    LineOfSight(fn(&S) -> bool),
+}
+
+/// The cells of an automaton, where all the cells which aren't stored have the background state.
+pub trait Storage<S> {
+    fn get(&self, pos: Pos) -> S;
+
+    fn set(&mut self, pos: Pos, state: S);
+
+    /// Checks if the cell is inside the stored area. Lines of sight end when leaving it.
+    fn contains(&self, pos: Pos) -> bool;
+
+    /// The cells which can change in the next step.
+    fn candidates(&self) -> Vec<Pos>;
+
+    /// The stored cells with their states.
+    fn cells(&self) -> Vec<(Pos, S)>;
+
+    fn background(&self) -> S;
+
+    /// Sets all the cells to the given background state.
+    fn clear(&mut self, background: S);
+
+    /// Extends the stored area by one cell on each side for infinite automata.
+    fn grow(&mut self);
+}
+
+/// A rectangle of cells starting at the origin, which is `(0, 0)` until the grid grows.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DenseGrid<S> {
+    origin: Pos,
+    rows: usize,
+    cols: usize,
+    cells: Vec<S>,
+    background: S,
+}
+
+impl<S: Copy> DenseGrid<S> {
+    pub fn from_rows(rows: Vec<Vec<S>>, background: S) -> Self {
+        let cols = rows.first().map(Vec::len).unwrap_or_default();
+        assert!(
+            rows.iter().all(|row| row.len() == cols),
+            "rows differ in length"
+        );
+
+        Self {
+            origin: (0, 0),
+            rows: rows.len(),
+            cols,
+            cells: rows.into_iter().flatten().collect(),
+            background,
+        }
+    }
+
+    pub fn to_rows(&self) -> Vec<Vec<S>> {
+        self.cells
+            .chunks(self.cols.max(1))
+            .map(<[S]>::to_vec)
+            .collect()
+    }
+
+    fn index(&self, (row, col): Pos) -> Option<usize> {
+        let row = usize::try_from(row - self.origin.0).ok()?;
+        let col = usize::try_from(col - self.origin.1).ok()?;
+        (row < self.rows && col < self.cols).then_some(row * self.cols + col)
+    }
+
+    fn pos(&self, idx: usize) -> Pos {
+        (
+            self.origin.0 + (idx / self.cols) as isize,
+            self.origin.1 + (idx % self.cols) as isize,
+        )
+    }
+}
+
+impl<S: Copy> Storage<S> for DenseGrid<S> {
+    fn get(&self, pos: Pos) -> S {
+        self.index(pos)
+            .map_or(self.background, |idx| self.cells[idx])
+    }
+
+    fn set(&mut self, pos: Pos, state: S) {
+        let idx = self
+            .index(pos)
+            .unwrap_or_else(|| panic!("{pos:?} is outside the grid"));
+        self.cells[idx] = state;
+    }
+
+    fn contains(&self, pos: Pos) -> bool {
+        self.index(pos).is_some()
+    }
+
+    fn candidates(&self) -> Vec<Pos> {
+        (0..self.cells.len()).map(|idx| self.pos(idx)).collect()
+    }
+
+    fn cells(&self) -> Vec<(Pos, S)> {
+        self.cells
+            .iter()
+            .enumerate()
+            .map(|(idx, state)| (self.pos(idx), *state))
+            .collect()
+    }
+
+    fn background(&self) -> S {
+        self.background
+    }
+
+    fn clear(&mut self, background: S) {
+        self.cells.fill(background);
+        self.background = background;
+    }
+
+    fn grow(&mut self) {
+        let mut grown = Self {
+            origin: (self.origin.0 - 1, self.origin.1 - 1),
+            rows: self.rows + 2,
+            cols: self.cols + 2,
+            cells: vec![self.background; (self.rows + 2) * (self.cols + 2)],
+            background: self.background,
+        };
+        for (pos, state) in self.cells() {
+            grown.set(pos, state);
+        }
+
+        *self = grown;
+    }
+}
+
+/// Stores only the cells which differ from the background, for automata spreading over a mostly
+/// empty plane. Only the stored cells and their adjacent cells are updated in each step, so the
+/// rule must keep a cell in the background state when it doesn't touch any stored cell.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SparseGrid<S> {
+    /// Fx hashing is far faster than the default for the small position keys.
+    cells: FxHashMap<Pos, S>,
+    background: S,
+    /// The top left and bottom right corners of the stored cells.
+    bounds: Option<(Pos, Pos)>,
+}
+
+impl<S: Copy + PartialEq> SparseGrid<S> {
+    pub fn new(background: S) -> Self {
+        Self {
+            cells: FxHashMap::default(),
+            background,
+            bounds: None,
+        }
+    }
+
+    pub fn from_cells(cells: impl IntoIterator<Item = (Pos, S)>, background: S) -> Self {
+        let mut grid = Self::new(background);
+        for (pos, state) in cells {
+            grid.set(pos, state);
+        }
+
+        grid
+    }
+
+    /// The stored cells without collecting them like [`Storage::cells`].
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, S)> + '_ {
+        self.cells.iter().map(|(pos, state)| (*pos, *state))
+    }
+}
+
+impl<S: Copy + PartialEq> Storage<S> for SparseGrid<S> {
+    fn get(&self, pos: Pos) -> S {
+        self.cells.get(&pos).copied().unwrap_or(self.background)
+    }
+
+    /// Setting a cell to the background removes it but keeps the bounds, which only shrink
+    /// when the grid is cleared.
+    fn set(&mut self, pos: Pos, state: S) {
+        if state == self.background {
+            self.cells.remove(&pos);
+            return;
+        }
+
+        self.cells.insert(pos, state);
+        let (min, max) = self.bounds.get_or_insert((pos, pos));
+        *min = (min.0.min(pos.0), min.1.min(pos.1));
+        *max = (max.0.max(pos.0), max.1.max(pos.1));
+    }
+
+    fn contains(&self, (row, col): Pos) -> bool {
+        self.bounds.is_some_and(|(min, max)| {
+            (min.0..=max.0).contains(&row) && (min.1..=max.1).contains(&col)
+        })
+    }
+
+    fn candidates(&self) -> Vec<Pos> {
+        let mut candidates: FxHashSet<Pos> = self.cells.keys().copied().collect();
+        for &(row, col) in self.cells.keys() {
+            candidates.extend(
+                ADJACENT
+                    .iter()
+                    .map(|(d_row, d_col)| (row + d_row, col + d_col)),
+            );
+        }
+
+        candidates.into_iter().collect()
+    }
+
+    fn cells(&self) -> Vec<(Pos, S)> {
+        self.iter().collect()
+    }
+
+    fn background(&self) -> S {
+        self.background
+    }
+
+    fn clear(&mut self, background: S) {
+        self.cells.clear();
+        self.background = background;
+        self.bounds = None;
+    }
+
+    fn grow(&mut self) {}
+}
+
+/// Rule computing the next state of a cell from its state and the states of its neighbours.
+pub type Rule<S> = fn(S, &[S]) -> S;
+
+#[derive(Debug, Clone)]
+pub struct Automaton<S, T, R = Rule<S>> {
+    storage: T,
+    neighbourhood: Neighbourhood<S>,
+    rule: R,
+    fixed: HashMap<Pos, S>,
+    infinite: bool,
+    steps: usize,
+}
+
+impl<S, T, R> Automaton<S, T, R>
+where
+    S: Copy + PartialEq,
+    T: Storage<S>,
+    R: Fn(S, &[S]) -> S,
+{
+    pub fn new(storage: T, neighbourhood: Neighbourhood<S>, rule: R) -> Self {
+        Self {
+            storage,
+            neighbourhood,
+            rule,
+            fixed: HashMap::new(),
+            infinite: false,
+            steps: 0,
+        }
+    }
+
+    /// Keeps the given cells in their states, starting right away.
+    pub fn with_fixed(mut self, cells: impl IntoIterator<Item = (Pos, S)>) -> Self {
+        for (pos, state) in cells {
+            self.storage.set(pos, state);
+            self.fixed.insert(pos, state);
+        }
+
+        self
+    }
+
+    /// Applies the rule to the background too, letting the pattern grow without limits.
+    pub fn infinite(mut self) -> Self {
+        self.infinite = true;
+        self
+    }
+
+    pub fn storage(&self) -> &T {
+        &self.storage
+    }
+
+    /// Count of the steps applied so far.
+    pub fn steps(&self) -> usize {
+        self.steps
+    }
+
+    /// Count of the stored cells in the given state.
+    pub fn count(&self, state: S) -> usize {
+        self.storage
+            .cells()
+            .iter()
+            .filter(|(_, cell)| *cell == state)
+            .count()
+    }
+
+    fn fill_neighbours(&self, (row, col): Pos, neighbours: &mut Vec<S>) {
+        neighbours.clear();
+        for (d_row, d_col) in ADJACENT {
+            let mut pos = (row + d_row, col + d_col);
+            if let Neighbourhood::LineOfSight(see_through) = self.neighbourhood {
+                while self.storage.contains(pos) && see_through(&self.storage.get(pos)) {
+                    pos = (pos.0 + d_row, pos.1 + d_col);
+                }
+            }
+            neighbours.push(self.storage.get(pos));
+        }
+    }
+
+    /// Applies the rule to all the cells at once. Returns if any cell has changed.
+    pub fn step(&mut self) -> bool {
+        if self.infinite {
+            self.storage.grow();
+        }
+
+        let mut neighbours = Vec::with_capacity(ADJACENT.len());
+        let mut changed = false;
+        let updates: Vec<_> = self
+            .storage
+            .candidates()
+            .into_iter()
+            .map(|pos| {
+                let state = self.storage.get(pos);
+                let next = match self.fixed.get(&pos) {
+                    Some(fixed) => *fixed,
+                    None => {
+                        self.fill_neighbours(pos, &mut neighbours);
+                        (self.rule)(state, &neighbours)
+                    }
+                };
+                changed |= next != state;
+                (pos, next)
+            })
+            .collect();
+
+        let mut background = self.storage.background();
+        if self.infinite {
+            let next = (self.rule)(background, &[background; ADJACENT.len()]);
+            changed |= next != background;
+            background = next;
+        }
+
+        self.storage.clear(background);
+        for (pos, state) in updates {
+            self.storage.set(pos, state);
+        }
+        for (pos, state) in &self.fixed {
+            self.storage.set(*pos, *state);
+        }
+        self.steps += 1;
+
+        changed
+    }
+
+    pub fn run(&mut self, steps: usize) {
+        for _ in 0..steps {
+            self.step();
+        }
+    }
+
+    /// Steps until nothing changes anymore, calling the observer after each step which has
+    /// changed any cell. Returns the count of these steps.
+    pub fn run_until_stable(&mut self, mut observe: impl FnMut(&Self)) -> usize {
+        let mut changing_steps = 0;
+        while self.step() {
+            changing_steps += 1;
+            observe(self);
+        }
+
+        changing_steps
+    }
+}
+
+/// Counts the neighbours in the given state.
+pub fn count_state<S: PartialEq>(neighbours: &[S], state: S) -> usize {
+    neighbours.iter().filter(|cell| **cell == state).count()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn life(alive: bool, neighbours: &[bool]) -> bool {
+        matches!((alive, count_state(neighbours, true)), (true, 2) | (_, 3))
+    }
+
+    fn parse(input: &str) -> Vec<Vec<bool>> {
+        input
+            .lines()
+            .map(|line| line.chars().map(|ch| ch == '#').collect())
+            .collect()
+    }
+
+    #[test]
+    fn test_dense_blinker() {
+        let grid = DenseGrid::from_rows(parse("...\n###\n..."), false);
+        let mut automaton = Automaton::new(grid, Neighbourhood::Adjacent, life as Rule<bool>);
+
+        assert!(automaton.step());
+        assert_eq!(automaton.storage().to_rows(), parse(".#.\n.#.\n.#."));
+        assert!(automaton.step());
+        assert_eq!(automaton.storage().to_rows(), parse("...\n###\n..."));
+        assert_eq!(automaton.count(true), 3);
+        assert_eq!(automaton.steps(), 2);
+    }
+
+    #[test]
+    fn test_sparse_glider() {
+        let glider = [(0, 1), (1, 2), (2, 0), (2, 1), (2, 2)].map(|pos| (pos, true));
+        let grid = SparseGrid::from_cells(glider, false);
+        let mut automaton = Automaton::new(grid, Neighbourhood::Adjacent, life as Rule<bool>);
+        automaton.run(4);
+
+        let mut cells: Vec<_> = automaton.storage().cells();
+        cells.sort_unstable();
+        assert_eq!(
+            cells,
+            glider.map(|((row, col), on)| ((row + 1, col + 1), on))
+        );
+        assert!(automaton.storage().contains((3, 3)));
+        assert!(!automaton.storage().contains((0, 0)));
+    }
+
+    #[test]
+    fn test_stable_and_fixed() {
+        let grid = DenseGrid::from_rows(parse("##.\n##.\n..."), false);
+        let mut automaton = Automaton::new(grid, Neighbourhood::Adjacent, life as Rule<bool>);
+        assert_eq!(automaton.run_until_stable(|_| {}), 0);
+
+        let grid = DenseGrid::from_rows(parse("...\n.#.\n..."), false);
+        let mut automaton = Automaton::new(grid, Neighbourhood::Adjacent, life as Rule<bool>)
+            .with_fixed([((0, 0), true)]);
+        assert_eq!(automaton.run_until_stable(|_| {}), 1);
+        assert_eq!(automaton.storage().to_rows(), parse("#..\n...\n..."));
+    }
+
+    #[test]
+    fn test_line_of_sight() {
+        // Zero is the floor, one an empty and two an occupied seat.
+        let grid = DenseGrid::from_rows(vec![vec![2, 0, 0, 1], vec![0, 0, 0, 0]], 0);
+        let automaton = Automaton::new(
+            grid,
+            Neighbourhood::LineOfSight(|state| *state == 0),
+            |state, _: &[u8]| state,
+        );
+
+        let mut neighbours = Vec::new();
+        automaton.fill_neighbours((0, 3), &mut neighbours);
+        assert_eq!(neighbours, [0, 0, 0, 2, 0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn test_infinite_background() {
+        // Every cell flips, including the infinite background.
+        let grid = DenseGrid::from_rows(parse("#"), false);
+        let mut automaton =
+            Automaton::new(grid, Neighbourhood::Adjacent, |on: bool, _: &[bool]| !on).infinite();
+        automaton.step();
+        assert_eq!(automaton.storage().to_rows(), parse("###\n#.#\n###"));
+        assert!(automaton.storage().background());
+        automaton.step();
+        assert_eq!(automaton.count(true), 1);
+        assert!(!automaton.storage().background());
+    }
+}
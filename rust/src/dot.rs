@@ -0,0 +1,204 @@
+//! Graphviz DOT export for the puzzles with graph inputs.
+//!
+//! Days call [`export()`] with a name and a function building the [`Graph`], which runs only if
+//! the binary is started with `--export-dot <path>`. Each graph is saved next to the given path
+//! with its name appended, e.g. the path `out/graph.dot` with the name `valves` gives
+//! `out/graph_valves.dot`, which can be rendered with `dot -Tsvg out/graph_valves.dot`.
+
+use std::{
+    env,
+    fmt::{Display, Write},
+    fs, io,
+    path::{Path, PathBuf},
+    sync::OnceLock,
+};
+
+use crate::utls::{find_arg_value, named_path};
+
+/// Node shapes to tell the kinds of the nodes apart.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Shape {
+    Ellipse,
+    Circle,
+    DoubleCircle,
+    Box,
+    Diamond,
+    Hexagon,
+    Octagon,
+    Triangle,
+    InvTriangle,
+}
+
+impl Display for Shape {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Shape::Ellipse => "ellipse",
+            Shape::Circle => "circle",
+            Shape::DoubleCircle => "doublecircle",
+            Shape::Box => "box",
+            Shape::Diamond => "diamond",
+            Shape::Hexagon => "hexagon",
+            Shape::Octagon => "octagon",
+            Shape::Triangle => "triangle",
+            Shape::InvTriangle => "invtriangle",
+        };
+
+        write!(f, "{name}")
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Node {
+    id: String,
+    label: Option<String>,
+    shape: Shape,
+}
+
+#[derive(Debug, Clone)]
+struct Edge {
+    from: String,
+    to: String,
+    label: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Graph {
+    directed: bool,
+    nodes: Vec<Node>,
+    edges: Vec<Edge>,
+}
+
+impl Graph {
+    pub fn directed() -> Self {
+        Self {
+            directed: true,
+            nodes: Vec::new(),
+            edges: Vec::new(),
+        }
+    }
+
+    pub fn undirected() -> Self {
+        Self {
+            directed: false,
+            ..Self::directed()
+        }
+    }
+
+    pub fn node(&mut self, id: impl Display, shape: Shape) {
+        self.nodes.push(Node {
+            id: id.to_string(),
+            label: None,
+            shape,
+        });
+    }
+
+    pub fn labelled_node(&mut self, id: impl Display, label: impl Display, shape: Shape) {
+        self.nodes.push(Node {
+            id: id.to_string(),
+            label: Some(label.to_string()),
+            shape,
+        });
+    }
+
+    pub fn edge(&mut self, from: impl Display, to: impl Display) {
+        self.edges.push(Edge {
+            from: from.to_string(),
+            to: to.to_string(),
+            label: None,
+        });
+    }
+
+    pub fn labelled_edge(&mut self, from: impl Display, to: impl Display, label: impl Display) {
+        self.edges.push(Edge {
+            from: from.to_string(),
+            to: to.to_string(),
+            label: Some(label.to_string()),
+        });
+    }
+
+    pub fn to_dot(&self) -> String {
+        let (kind, arrow) = if self.directed {
+            ("digraph", "->")
+        } else {
+            ("graph", "--")
+        };
+
+        let mut dot = format!("{kind} {{\n");
+        for node in &self.nodes {
+            write!(dot, "    {} [shape={}", quote(&node.id), node.shape).unwrap();
+            if let Some(label) = &node.label {
+                write!(dot, ", label={}", quote(label)).unwrap();
+            }
+            dot.push_str("];\n");
+        }
+        for edge in &self.edges {
+            write!(dot, "    {} {arrow} {}", quote(&edge.from), quote(&edge.to)).unwrap();
+            if let Some(label) = &edge.label {
+                write!(dot, " [label={}]", quote(label)).unwrap();
+            }
+            dot.push_str(";\n");
+        }
+        dot.push_str("}\n");
+
+        dot
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        fs::write(path, self.to_dot())
+    }
+}
+
+fn quote(text: &str) -> String {
+    let escaped = text
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n");
+    format!("\"{escaped}\"")
+}
+
+static EXPORT_PATH: OnceLock<Option<PathBuf>> = OnceLock::new();
+
+/// Saves the graph returned by the build function if the export is requested.
+/// The graph is only built when it will be saved.
+pub fn export(name: &str, build: impl FnOnce() -> Graph) {
+    let Some(path) = EXPORT_PATH
+        .get_or_init(|| find_arg_value(env::args().skip(1), "--export-dot").map(PathBuf::from))
+    else {
+        return;
+    };
+
+    let path = named_path(path, name);
+    match build().save(&path) {
+        Ok(()) => println!("Graph saved to {}", path.display()),
+        Err(err) => eprintln!("Saving graph to {} failed: {err}", path.display()),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_to_dot() {
+        let mut graph = Graph::directed();
+        graph.node("in", Shape::Box);
+        graph.labelled_node("A", "Accept\n\"A\"", Shape::DoubleCircle);
+        graph.labelled_edge("in", "A", "x>10");
+        graph.edge("A", "in");
+
+        assert_eq!(
+            graph.to_dot(),
+            r#"digraph {
+    "in" [shape=box];
+    "A" [shape=doublecircle, label="Accept\n\"A\""];
+    "in" -> "A" [label="x>10"];
+    "A" -> "in";
+}
+"#
+        );
+
+        let mut graph = Graph::undirected();
+        graph.edge("a", "b");
+        assert_eq!(graph.to_dot(), "graph {\n    \"a\" -- \"b\";\n}\n");
+    }
+}
@@ -0,0 +1,162 @@
+//! Image export for grid states which are too large to read in the terminal.
+//!
+//! Days call [`export()`] with a name and a function building the [`Image`], which runs only if
+//! the binary is started with `--export-image <path>`. Each image is saved next to the given
+//! path with its name appended, e.g. the path `out/grid.ppm` with the name `day_06` gives
+//! `out/grid_day_06.ppm`. Images are saved as plain PPM, or as PNG if the path ends with `.png`
+//! and the `png` feature is enabled.
+
+use std::{
+    env, fs, io,
+    path::{Path, PathBuf},
+    sync::OnceLock,
+};
+
+use crate::utls::{find_arg_value, named_path};
+
+pub type Rgb = [u8; 3];
+
+pub const BLACK: Rgb = [0, 0, 0];
+pub const WHITE: Rgb = [255, 255, 255];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Image {
+    width: usize,
+    height: usize,
+    pixels: Vec<Rgb>,
+}
+
+impl Image {
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![BLACK; width * height],
+        }
+    }
+
+    /// Creates an image with a pixel for each cell, colored by the palette.
+    pub fn from_grid<T>(rows: &[Vec<T>], palette: impl Fn(&T) -> Rgb) -> Self {
+        let width = rows.iter().map(Vec::len).max().unwrap_or_default();
+        let mut image = Self::new(width, rows.len());
+        for (y, row) in rows.iter().enumerate() {
+            for (x, cell) in row.iter().enumerate() {
+                image.set(x, y, palette(cell));
+            }
+        }
+
+        image
+    }
+
+    pub fn set(&mut self, x: usize, y: usize, color: Rgb) {
+        assert!(
+            x < self.width && y < self.height,
+            "({x}, {y}) is out of image"
+        );
+        self.pixels[y * self.width + x] = color;
+    }
+
+    /// Enlarges the image so each pixel becomes a square with the given side.
+    pub fn scale(&self, factor: usize) -> Self {
+        let mut image = Self::new(self.width * factor, self.height * factor);
+        for y in 0..image.height {
+            for x in 0..image.width {
+                image.set(x, y, self.pixels[(y / factor) * self.width + x / factor]);
+            }
+        }
+
+        image
+    }
+
+    fn to_ppm(&self) -> Vec<u8> {
+        let mut bytes = format!("P6\n{} {}\n255\n", self.width, self.height).into_bytes();
+        bytes.extend(self.pixels.iter().flatten());
+
+        bytes
+    }
+
+    #[cfg(feature = "png")]
+    fn to_png(&self) -> io::Result<Vec<u8>> {
+        let mut bytes = Vec::new();
+        let mut encoder = png::Encoder::new(&mut bytes, self.width as u32, self.height as u32);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+
+        let mut writer = encoder.write_header().map_err(io::Error::other)?;
+        let data: Vec<u8> = self.pixels.iter().flatten().copied().collect();
+        writer.write_image_data(&data).map_err(io::Error::other)?;
+        writer.finish().map_err(io::Error::other)?;
+
+        Ok(bytes)
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let is_png = path.extension().is_some_and(|ext| ext == "png");
+        if is_png {
+            #[cfg(feature = "png")]
+            return fs::write(path, self.to_png()?);
+
+            #[cfg(not(feature = "png"))]
+            return Err(io::Error::other("PNG export needs the `png` feature"));
+        }
+
+        fs::write(path, self.to_ppm())
+    }
+}
+
+/// Black for off and white for on.
+pub fn binary(on: bool) -> Rgb {
+    if on { WHITE } else { BLACK }
+}
+
+/// Maps the value from black at zero over red and yellow to white at max.
+pub fn heat(value: u64, max: u64) -> Rgb {
+    let level = value.min(max) * 765 / max.max(1);
+    let channel = |offset: u64| level.saturating_sub(offset).min(255) as u8;
+    [channel(0), channel(255), channel(510)]
+}
+
+static EXPORT_PATH: OnceLock<Option<PathBuf>> = OnceLock::new();
+
+/// Saves the image returned by the build function if the export is requested.
+/// The image is only built when it will be saved.
+pub fn export(name: &str, build: impl FnOnce() -> Image) {
+    let Some(path) = EXPORT_PATH
+        .get_or_init(|| find_arg_value(env::args().skip(1), "--export-image").map(PathBuf::from))
+    else {
+        return;
+    };
+
+    let path = named_path(path, name);
+    match build().save(&path) {
+        Ok(()) => println!("Image saved to {}", path.display()),
+        Err(err) => eprintln!("Saving image to {} failed: {err}", path.display()),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_ppm() {
+        let image = Image::from_grid(&[vec![true, false], vec![false, true]], |on| binary(*on));
+        let mut expected = b"P6\n2 2\n255\n".to_vec();
+        expected.extend([255, 255, 255, 0, 0, 0, 0, 0, 0, 255, 255, 255]);
+        assert_eq!(image.to_ppm(), expected);
+
+        let scaled = image.scale(2);
+        assert_eq!((scaled.width, scaled.height), (4, 4));
+        assert_eq!(scaled.pixels[5], WHITE);
+        assert_eq!(scaled.pixels[2], BLACK);
+    }
+
+    #[test]
+    fn test_palette() {
+        assert_eq!(heat(20, 3), WHITE);
+        assert_eq!(heat(0, 3), BLACK);
+        assert_eq!(heat(1, 3), [255, 0, 0]);
+        assert_eq!(heat(2, 3), [255, 255, 0]);
+        assert_eq!(heat(3, 3), WHITE);
+    }
+}
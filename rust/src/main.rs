@@ -1,3 +1,7 @@
+mod anim;
+mod automaton;
+mod dot;
+mod image;
 mod utls;
 mod year_15;
 mod year_16;
@@ -1,6 +1,7 @@
 use std::{
     fs,
     io::{self, BufRead},
+    path::{Path, PathBuf},
 };
 
 // I can't use this macro because I don't save the input files within the git repository. In case I
@@ -33,3 +34,59 @@ pub fn read_text_from_file(year: &str, day: &str) -> String {
 
     fs::read_to_string(path).unwrap()
 }
+
+/// Returns the value following the given option in the command line arguments.
+pub fn find_arg_value(args: impl IntoIterator<Item = String>, option: &str) -> Option<String> {
+    let mut args = args.into_iter();
+    args.find(|arg| arg == option)?;
+
+    Some(
+        args.next()
+            .unwrap_or_else(|| panic!("{option} needs a value")),
+    )
+}
+
+/// Checks if the flag is among the command line arguments.
+pub fn has_flag(args: impl IntoIterator<Item = String>, flag: &str) -> bool {
+    args.into_iter().any(|arg| arg == flag)
+}
+
+/// Inserts the name between the file stem and the extension of the path.
+pub fn named_path(path: &Path, name: &str) -> PathBuf {
+    let stem = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy())
+        .unwrap_or_default();
+    let mut file_name = format!("{stem}_{name}");
+    if let Some(ext) = path.extension() {
+        file_name.push('.');
+        file_name.push_str(&ext.to_string_lossy());
+    }
+
+    path.with_file_name(file_name)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_paths() {
+        let args = ["--animate", "--export-image", "out/grid.ppm"].map(String::from);
+        let path = PathBuf::from(find_arg_value(args, "--export-image").unwrap());
+        assert_eq!(
+            named_path(&path, "day_06"),
+            PathBuf::from("out/grid_day_06.ppm")
+        );
+        assert_eq!(
+            find_arg_value(["--animate".to_owned()], "--export-image"),
+            None
+        );
+        assert!(has_flag(["--step".to_owned()], "--step"));
+        assert!(!has_flag(["--steps".to_owned()], "--step"));
+        assert_eq!(
+            named_path(Path::new("grid"), "tree"),
+            PathBuf::from("grid_tree")
+        );
+    }
+}
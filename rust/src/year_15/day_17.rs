@@ -2,9 +2,14 @@
 
 use itertools::Itertools;
 
-const INPUT: [u16; 20] = [
-    33, 14, 18, 20, 45, 35, 16, 35, 1, 13, 18, 13, 50, 44, 48, 6, 24, 41, 30, 42,
-];
+use crate::utls::read_text_from_file;
+
+fn fetch_input() -> Vec<u16> {
+    read_text_from_file("15", "17")
+        .lines()
+        .map(|line| line.trim().parse().unwrap())
+        .collect()
+}
 
 fn get_range_min(goal: u16, mut input: Vec<u16>) -> usize {
     input.sort();
@@ -55,14 +60,14 @@ fn calc_min_combi_count(goal: u16, input: Vec<u16>) -> usize {
 }
 
 fn part_1() {
-    let mut input = INPUT.to_vec();
+    let mut input = fetch_input();
     let combinations = calc_combi_count(150, input);
 
     println!("combinations are {combinations}");
 }
 
 fn part_2() {
-    let mut input = INPUT.to_vec();
+    let mut input = fetch_input();
     let combinations = calc_min_combi_count(150, input);
 
     println!("min combinations are {combinations}");
@@ -1,6 +1,9 @@
 #![allow(warnings, unused)]
 
-use crate::utls::read_lines_from_file;
+use crate::{
+    automaton::{Automaton, DenseGrid, Neighbourhood, Rule, count_state},
+    utls::read_lines_from_file,
+};
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 enum State {
@@ -18,115 +21,46 @@ impl From<char> for State {
     }
 }
 
+type Lights = Automaton<State, DenseGrid<State>>;
+
 #[derive(Debug, Clone)]
 struct LightsGrid {
-    cells: Vec<Vec<State>>,
+    automaton: Lights,
 }
 
 impl LightsGrid {
     fn new(cells: Vec<Vec<State>>) -> Self {
-        Self { cells }
+        let grid = DenseGrid::from_rows(cells, State::Off);
+        let automaton = Automaton::new(grid, Neighbourhood::Adjacent, Self::rule as Rule<State>);
+        Self { automaton }
     }
 
-    fn get_cell(&self, row: usize, col: usize) -> State {
-        self.cells[row][col]
+    /// Keeps the four corners stuck on.
+    fn with_stuck_corners(self) -> Self {
+        let rows = self.automaton.storage().to_rows();
+        let last_row = rows.len() as isize - 1;
+        let last_col = rows[0].len() as isize - 1;
+        let corners = [(0, 0), (0, last_col), (last_row, 0), (last_row, last_col)];
+
+        let automaton = self
+            .automaton
+            .with_fixed(corners.map(|corner| (corner, State::On)));
+        Self { automaton }
     }
 
-    fn get_cell_or_off(&self, row: i32, col: i32) -> State {
-        // casting negative int will give usize::Max - int
-        let row = row as usize;
-        let col = col as usize;
-
-        if row < self.cells.len() && col < self.cells[0].len() {
-            self.get_cell(row, col)
-        } else {
-            State::Off
+    fn rule(state: State, neighbours: &[State]) -> State {
+        match (state, count_state(neighbours, State::On)) {
+            (State::On, 2 | 3) | (State::Off, 3) => State::On,
+            _ => State::Off,
         }
     }
 
-    fn set_cell(&mut self, row: usize, col: usize, value: State) {
-        self.cells[row][col] = value;
-    }
-
     fn get_on_lights_count(&self) -> usize {
-        self.cells
-            .iter()
-            .flatten()
-            .filter(|&cell| *cell == State::On)
-            .count()
-    }
-
-    fn get_neighbors_on_count(&self, row: usize, col: usize) -> u8 {
-        let row = row as i32;
-        let col = col as i32;
-
-        let mut on_count = 0;
-        for r in row - 1..=row + 1 {
-            for c in col - 1..=col + 1 {
-                if r == row && c == col {
-                    continue;
-                }
-                if self.get_cell_or_off(r, c) == State::On {
-                    on_count += 1;
-                }
-            }
-        }
-
-        on_count
+        self.automaton.count(State::On)
     }
 
     fn do_step(&mut self) {
-        let clone = self.clone();
-        for row in 0..self.cells.len() {
-            for col in 0..self.cells[0].len() {
-                let on_neighbors = clone.get_neighbors_on_count(row, col);
-                match clone.get_cell(row, col) {
-                    State::On => {
-                        if on_neighbors != 2 && on_neighbors != 3 {
-                            self.set_cell(row, col, State::Off);
-                        }
-                    }
-                    State::Off => {
-                        if on_neighbors == 3 {
-                            self.set_cell(row, col, State::On);
-                        }
-                    }
-                }
-            }
-        }
-    }
-
-    fn do_step_part_2(&mut self) {
-        let last = self.cells.len() - 1;
-        self.set_cell(0, 0, State::On);
-        self.set_cell(0, last, State::On);
-        self.set_cell(last, 0, State::On);
-        self.set_cell(last, last, State::On);
-
-        let clone = self.clone();
-        for row in 0..self.cells.len() {
-            for col in 0..self.cells[0].len() {
-                let last_row = self.cells.len() - 1;
-                let last_col = self.cells[0].len() - 1;
-                if (row == 0 || row == last_row) && (col == 0 || col == last_col) {
-                    continue;
-                }
-
-                let on_neighbors = clone.get_neighbors_on_count(row, col);
-                match clone.get_cell(row, col) {
-                    State::On => {
-                        if on_neighbors != 2 && on_neighbors != 3 {
-                            self.set_cell(row, col, State::Off);
-                        }
-                    }
-                    State::Off => {
-                        if on_neighbors == 3 {
-                            self.set_cell(row, col, State::On);
-                        }
-                    }
-                }
-            }
-        }
+        self.automaton.step();
     }
 }
 
@@ -148,9 +82,9 @@ fn part_1() {
     println!("part one answer is {on_count}");
 }
 fn part_2() {
-    let mut input = fetch_input();
+    let mut input = fetch_input().with_stuck_corners();
     for _ in 0..100 {
-        input.do_step_part_2();
+        input.do_step();
     }
 
     let on_count = input.get_on_lights_count();
@@ -195,9 +129,9 @@ mod tests {
 
     #[test]
     fn test_part_two() {
-        let mut input = get_test_input();
+        let mut input = get_test_input().with_stuck_corners();
         for _ in 0..5 {
-            input.do_step_part_2();
+            input.do_step();
         }
         let on_count = input.get_on_lights_count();
 
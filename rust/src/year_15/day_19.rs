@@ -1,7 +1,6 @@
 #![allow(warnings, unused)]
 
-use rand::{seq::SliceRandom, thread_rng};
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
 use crate::utls::read_lines_from_file;
 
@@ -60,45 +59,319 @@ fn part_1() {
     println!("distinct molecule count is {molecule_count}");
 }
 
-fn reverse_replacement(repl: &Replacement, molecule: &mut String) -> bool {
-    if let Some(index) = molecule.find(&repl.result) {
-        molecule.replace_range(index..index + repl.result.len(), &repl.base);
-        true
-    } else {
-        false
+/// Splits a molecule into its elements, e.g. "CRnAl" -> ["C", "Rn", "Al"]
+fn tokenize(molecule: &str) -> Vec<&str> {
+    let mut tokens = Vec::new();
+    let mut start = 0;
+    for (idx, ch) in molecule.char_indices().skip(1) {
+        if ch.is_ascii_uppercase() {
+            tokens.push(&molecule[start..idx]);
+            start = idx;
+        }
+    }
+    if !molecule.is_empty() {
+        tokens.push(&molecule[start..]);
+    }
+
+    tokens
+}
+
+#[derive(Debug)]
+struct Rule {
+    lhs: usize,
+    rhs: Vec<usize>,
+}
+
+#[derive(Debug, Default)]
+struct Grammar {
+    symbols: HashMap<String, usize>,
+    names: Vec<String>,
+    rules: Vec<Rule>,
+}
+
+impl Grammar {
+    fn new(replacements: &[Replacement]) -> Self {
+        let mut grammar = Self::default();
+        for repl in replacements {
+            let lhs = grammar.symbol(&repl.base);
+            let rhs = tokenize(&repl.result)
+                .into_iter()
+                .map(|token| grammar.symbol(token))
+                .collect();
+            grammar.rules.push(Rule { lhs, rhs });
+        }
+
+        grammar
+    }
+
+    fn symbol(&mut self, name: &str) -> usize {
+        if let Some(&id) = self.symbols.get(name) {
+            return id;
+        }
+        let id = self.names.len();
+        self.names.push(name.to_owned());
+        self.symbols.insert(name.to_owned(), id);
+        id
     }
 }
 
-fn find_any_count_rand(input: String, mut replacements: Vec<Replacement>) -> usize {
-    loop {
-        let mut cloned_input = input.clone();
-        let mut count = 0;
-        let mut done = false;
-        while !done {
-            done = true;
-            for repl in replacements.iter() {
-                if reverse_replacement(repl, &mut cloned_input) {
-                    count += 1;
-                    done = false;
+/// How the cheapest derivation of a symbol over a span was built.
+#[derive(Debug, Clone, Copy)]
+enum Back {
+    Token,
+    Unit { rule: usize, from: usize },
+    Rule { rule: usize, mid: usize },
+}
+
+/// A rule whose first `len` symbols derive the span.
+#[derive(Debug, Clone, Copy)]
+struct Prefix {
+    rule: usize,
+    len: usize,
+    cost: usize,
+    mid: usize,
+}
+
+/// Chart of a weighted CYK parse, where each applied rule costs one step.
+struct Chart<'a> {
+    grammar: &'a Grammar,
+    size: usize,
+    best: Vec<Option<(usize, Back)>>,
+    prefixes: Vec<Vec<Prefix>>,
+}
+
+impl<'a> Chart<'a> {
+    fn parse(grammar: &'a Grammar, tokens: &[usize]) -> Self {
+        let size = tokens.len() + 1;
+        let sym_count = grammar.names.len();
+        let mut chart = Self {
+            grammar,
+            size,
+            best: vec![None; size * size * sym_count],
+            prefixes: vec![Vec::new(); size * size],
+        };
+
+        for len in 1..size {
+            for start in 0..=tokens.len() - len {
+                let end = start + len;
+                if len == 1 {
+                    let idx = chart.best_idx(start, end, tokens[start]);
+                    chart.best[idx] = Some((0, Back::Token));
+                }
+
+                let mut extended: HashMap<(usize, usize), (usize, usize)> = HashMap::new();
+                for mid in start + 1..end {
+                    for prefix in &chart.prefixes[chart.span_idx(start, mid)] {
+                        let rhs = &grammar.rules[prefix.rule].rhs;
+                        let Some((cost, _)) = chart.best[chart.best_idx(mid, end, rhs[prefix.len])]
+                        else {
+                            continue;
+                        };
+                        let cost = cost + prefix.cost;
+                        let entry = extended
+                            .entry((prefix.rule, prefix.len + 1))
+                            .or_insert((cost, mid));
+                        if cost < entry.0 {
+                            *entry = (cost, mid);
+                        }
+                    }
+                }
+
+                let mut prefixes = Vec::new();
+                for ((rule, len), (cost, mid)) in extended {
+                    let rule_def = &grammar.rules[rule];
+                    if len == rule_def.rhs.len() {
+                        chart.relax(start, end, rule_def.lhs, cost + 1, Back::Rule { rule, mid });
+                    } else {
+                        prefixes.push(Prefix {
+                            rule,
+                            len,
+                            cost,
+                            mid,
+                        });
+                    }
+                }
+
+                chart.close_units(start, end);
+
+                for (rule, rule_def) in grammar.rules.iter().enumerate() {
+                    if rule_def.rhs.len() < 2 {
+                        continue;
+                    }
+                    if let Some((cost, _)) = chart.best[chart.best_idx(start, end, rule_def.rhs[0])]
+                    {
+                        prefixes.push(Prefix {
+                            rule,
+                            len: 1,
+                            cost,
+                            mid: end,
+                        });
+                    }
+                }
+
+                let span = chart.span_idx(start, end);
+                chart.prefixes[span] = prefixes;
+            }
+        }
+
+        chart
+    }
+
+    fn span_idx(&self, start: usize, end: usize) -> usize {
+        start * self.size + end
+    }
+
+    fn best_idx(&self, start: usize, end: usize, sym: usize) -> usize {
+        self.span_idx(start, end) * self.grammar.names.len() + sym
+    }
+
+    fn relax(&mut self, start: usize, end: usize, sym: usize, cost: usize, back: Back) -> bool {
+        let idx = self.best_idx(start, end, sym);
+        match self.best[idx] {
+            Some((current, _)) if current <= cost => false,
+            _ => {
+                self.best[idx] = Some((cost, back));
+                true
+            }
+        }
+    }
+
+    /// Applies rules with a single symbol on the right side until nothing gets cheaper.
+    fn close_units(&mut self, start: usize, end: usize) {
+        let grammar = self.grammar;
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for (rule, rule_def) in grammar.rules.iter().enumerate() {
+                if rule_def.rhs.len() != 1 {
+                    continue;
+                }
+                let from = rule_def.rhs[0];
+                if let Some((cost, _)) = self.best[self.best_idx(start, end, from)] {
+                    changed |= self.relax(
+                        start,
+                        end,
+                        rule_def.lhs,
+                        cost + 1,
+                        Back::Unit { rule, from },
+                    );
+                }
+            }
+        }
+    }
+
+    fn build(&self, start: usize, end: usize, sym: usize) -> Node {
+        let (_, back) = self.best[self.best_idx(start, end, sym)].unwrap();
+        match back {
+            Back::Token => Node {
+                sym,
+                rule: None,
+                children: Vec::new(),
+            },
+            Back::Unit { rule, from } => Node {
+                sym,
+                rule: Some(rule),
+                children: vec![self.build(start, end, from)],
+            },
+            Back::Rule { rule, mid } => {
+                let rhs = &self.grammar.rules[rule].rhs;
+                let mut children = self.build_prefix(start, mid, rule, rhs.len() - 1);
+                children.push(self.build(mid, end, rhs[rhs.len() - 1]));
+                Node {
+                    sym,
+                    rule: Some(rule),
+                    children,
                 }
             }
         }
+    }
 
-        if cloned_input.len() == 1 {
-            return count;
-        } else {
-            replacements.shuffle(&mut thread_rng());
+    fn build_prefix(&self, start: usize, end: usize, rule: usize, len: usize) -> Vec<Node> {
+        let rhs = &self.grammar.rules[rule].rhs;
+        if len == 1 {
+            return vec![self.build(start, end, rhs[0])];
         }
+
+        let prefix = self.prefixes[self.span_idx(start, end)]
+            .iter()
+            .find(|prefix| prefix.rule == rule && prefix.len == len)
+            .unwrap();
+        let mut children = self.build_prefix(start, prefix.mid, rule, len - 1);
+        children.push(self.build(prefix.mid, end, rhs[len - 1]));
+
+        children
     }
+}
 
-    unreachable!("Nothing found");
+#[derive(Debug)]
+struct Node {
+    sym: usize,
+    rule: Option<usize>,
+    children: Vec<Node>,
+}
+
+#[derive(Debug)]
+struct ReductionStep {
+    replacement: Replacement,
+    molecule: String,
+}
+
+fn collect_reductions(
+    node: &Node,
+    pos: usize,
+    grammar: &Grammar,
+    replacements: &[Replacement],
+    current: &mut Vec<usize>,
+    steps: &mut Vec<ReductionStep>,
+) {
+    let Some(rule) = node.rule else {
+        return;
+    };
+
+    for (offset, child) in node.children.iter().enumerate() {
+        collect_reductions(child, pos + offset, grammar, replacements, current, steps);
+    }
+
+    current.splice(pos..pos + node.children.len(), [node.sym]);
+    steps.push(ReductionStep {
+        replacement: replacements[rule].clone(),
+        molecule: current
+            .iter()
+            .map(|&sym| grammar.names[sym].as_str())
+            .collect(),
+    });
+}
+
+/// Finds the shortest sequence of reverse replacements which reduces the molecule to "e".
+fn find_shortest_reduction(
+    input: &str,
+    replacements: &[Replacement],
+) -> Option<Vec<ReductionStep>> {
+    let mut grammar = Grammar::new(replacements);
+    let start = grammar.symbol("e");
+    let mut tokens: Vec<_> = tokenize(input)
+        .into_iter()
+        .map(|token| grammar.symbol(token))
+        .collect();
+    if tokens.is_empty() {
+        return None;
+    }
+
+    let chart = Chart::parse(&grammar, &tokens);
+    chart.best[chart.best_idx(0, tokens.len(), start)]?;
+
+    let root = chart.build(0, tokens.len(), start);
+    let mut steps = Vec::new();
+    collect_reductions(&root, 0, &grammar, replacements, &mut tokens, &mut steps);
+
+    Some(steps)
 }
 
 fn part_2() {
     let (replacements, input) = fetch_input();
-    let count = find_any_count_rand(input, replacements);
+    let steps = find_shortest_reduction(&input, &replacements).expect("molecule can't be made");
 
-    println!("resloved in {count} moves");
+    println!("resloved in {} moves", steps.len());
 }
 
 pub fn run() {
@@ -144,6 +417,36 @@ O => HH"
         let replacements = get_test_replacements(2);
         let input = "HOH".to_owned();
 
-        assert_eq!(find_any_count_rand(input, replacements), 3);
+        let steps = find_shortest_reduction(&input, &replacements).unwrap();
+        assert_eq!(steps.len(), 3);
+        assert_eq!(steps.last().unwrap().molecule, "e");
+
+        let steps = find_shortest_reduction("HOHOHO", &replacements).unwrap();
+        assert_eq!(steps.len(), 6);
+
+        let mut molecule = String::from("HOHOHO");
+        for step in steps {
+            let Replacement { base, result } = &step.replacement;
+            let is_valid = (0..molecule.len()).any(|idx| {
+                molecule[idx..].starts_with(result.as_str())
+                    && format!(
+                        "{}{base}{}",
+                        &molecule[..idx],
+                        &molecule[idx + result.len()..]
+                    ) == step.molecule
+            });
+            assert!(is_valid);
+            molecule = step.molecule;
+        }
+        assert_eq!(molecule, "e");
+
+        assert!(find_shortest_reduction("HX", &replacements).is_none());
+    }
+
+    #[test]
+    fn test_tokenize() {
+        assert_eq!(tokenize("CRnAlAr"), ["C", "Rn", "Al", "Ar"]);
+        assert_eq!(tokenize("e"), ["e"]);
+        assert_eq!(tokenize("HOH"), ["H", "O", "H"]);
     }
 }
@@ -1,8 +1,8 @@
 #![allow(warnings, unused)]
 
-use rand::{
-    rngs::ThreadRng,
-    seq::{IndexedRandom, SliceRandom},
+use std::{
+    cmp::Ordering,
+    collections::{BinaryHeap, HashSet},
 };
 
 #[derive(Debug, Clone)]
@@ -139,6 +139,7 @@ impl Wizard {
     }
 }
 
+#[derive(Debug, Clone)]
 struct Game {
     wizard: Wizard,
     boss: Boss,
@@ -217,16 +218,6 @@ impl Game {
         }
     }
 
-    fn get_valid_input(&self, rng: &mut ThreadRng) -> String {
-        let choices = ["m", "d", "s", "p", "r"];
-        loop {
-            let input = *choices.choose(rng).unwrap();
-            if self.is_input_valid(input) {
-                return input.into();
-            }
-        }
-    }
-
     fn is_input_valid(&self, input: &str) -> bool {
         match input {
             "m" => Wizard::MAGIC_MISSILE_COST <= self.wizard.mana,
@@ -238,75 +229,136 @@ impl Game {
         }
     }
 
-    fn simulate_battle(&mut self, rng: &mut ThreadRng) -> bool {
-        while self.still_playing() {
-            if self.wizard.mana < Wizard::MAGIC_MISSILE_COST {
-                return false;
-            }
-            let input = self.get_valid_input(rng);
-            self.play_wizard_turn(&input);
+    /// Everything that matters for the rest of the battle.
+    fn state_key(&self) -> (i16, i16, i16, i8, i8, i8) {
+        (
+            self.wizard.hit_points,
+            self.wizard.mana,
+            self.boss.hit_points,
+            self.wizard.shield_count,
+            self.wizard.poison_count,
+            self.wizard.recharge_count,
+        )
+    }
+}
 
-            if !self.still_playing() {
-                break;
-            }
+const SPELLS: [&str; 5] = ["m", "d", "s", "p", "r"];
 
-            self.play_boss_turn();
-        }
+#[derive(Debug)]
+struct SearchNode {
+    game: Game,
+    spells: Vec<&'static str>,
+    won: bool,
+}
+
+impl PartialEq for SearchNode {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
 
-        self.is_wizard_winner()
+impl Eq for SearchNode {}
+
+impl PartialOrd for SearchNode {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for SearchNode {
+    // Reversed to get the cheapest node first out of the max heap
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .game
+            .wizard
+            .spent_mana
+            .cmp(&self.game.wizard.spent_mana)
+            .then_with(|| self.won.cmp(&other.won))
     }
+}
 
-    fn simulate_battle_hard(&mut self, rng: &mut ThreadRng) -> bool {
-        while self.still_playing() {
-            if self.wizard.mana < Wizard::MAGIC_MISSILE_COST {
-                return false;
+/// Dijkstra over the battle states returning the least mana needed to win with the spells cast.
+fn find_min_mana(game: Game, hard: bool) -> Option<(i16, Vec<&'static str>)> {
+    let mut visited = HashSet::new();
+    let mut queue = BinaryHeap::new();
+    queue.push(SearchNode {
+        game,
+        spells: Vec::new(),
+        won: false,
+    });
+
+    while let Some(node) = queue.pop() {
+        if node.won {
+            return Some((node.game.wizard.spent_mana, node.spells));
+        }
+
+        if !visited.insert(node.game.state_key()) {
+            continue;
+        }
+
+        let mut game = node.game;
+        if hard {
+            game.wizard.hit_points -= 1;
+            if !game.wizard.hit_points.is_positive() {
+                continue;
             }
+        }
 
-            self.wizard.hit_points -= 1;
-            if !self.wizard.hit_points.is_positive() {
-                return false;
+        for spell in SPELLS {
+            if !game.is_input_valid(spell) {
+                continue;
             }
 
-            let input = self.get_valid_input(rng);
-            self.play_wizard_turn(&input);
+            let mut next = game.clone();
+            let mut spells = node.spells.clone();
+            spells.push(spell);
 
-            if !self.still_playing() {
-                break;
+            next.play_wizard_turn(spell);
+            if next.still_playing() {
+                next.play_boss_turn();
             }
 
-            self.play_boss_turn();
+            let won = !next.still_playing() && next.is_wizard_winner();
+            if next.still_playing() || won {
+                queue.push(SearchNode {
+                    game: next,
+                    spells,
+                    won,
+                });
+            }
         }
-
-        self.is_wizard_winner()
     }
+
+    None
 }
 
-fn part_1() {
-    let mut min_mana = i16::MAX;
-    let mut rng = rand::thread_rng();
-    //try 1000_000 times
-    for _ in 0..1000_000 {
-        let mut game = Game::new(Wizard::create_from_input(), Boss::create_from_input());
-        if game.simulate_battle(&mut rng) {
-            min_mana = min_mana.min(game.wizard.spent_mana);
-        }
+fn spell_name(spell: &str) -> &'static str {
+    match spell {
+        "m" => "Magic Missile",
+        "d" => "Drain",
+        "s" => "Shield",
+        "p" => "Poison",
+        "r" => "Recharge",
+        _ => panic!("invalid input"),
     }
+}
+
+fn print_solution(label: &str, game: Game, hard: bool) {
+    let (mana, spells) = find_min_mana(game, hard).expect("battle can't be won");
+    let spells: Vec<_> = spells.into_iter().map(spell_name).collect();
 
-    println!("after million simulation: {min_mana}");
+    println!("{label}: min mana is {mana}");
+    println!("    spells: {}", spells.join(", "));
 }
 
-fn part_2() {
-    let mut min_mana = i16::MAX;
-    let mut rng = rand::thread_rng();
-    //try 1000_000 times
-    for _ in 0..1000_000 {
-        let mut game = Game::new(Wizard::create_from_input(), Boss::create_from_input());
-        if game.simulate_battle_hard(&mut rng) {
-            min_mana = min_mana.min(game.wizard.spent_mana);
-        }
-    }
+fn part_1() {
+    let game = Game::new(Wizard::create_from_input(), Boss::create_from_input());
+    print_solution("part 1", game, false);
+}
 
-    println!("after million simulation on hard: {min_mana}");
+fn part_2() {
+    let game = Game::new(Wizard::create_from_input(), Boss::create_from_input());
+    print_solution("part 2", game, true);
 }
 
 pub fn run() {
@@ -365,4 +417,29 @@ mod tests {
         assert!(!game.still_playing());
         assert!(game.is_wizard_winner());
     }
+
+    #[test]
+    fn test_find_min_mana() {
+        let game = Game::new(Wizard::new(10, 250), Boss::new(13, 8));
+        let (mana, spells) = find_min_mana(game, false).unwrap();
+        assert_eq!(mana, 226);
+        assert_eq!(spells, ["p", "m"]);
+
+        let game = Game::new(Wizard::new(10, 250), Boss::new(14, 8));
+        let (mana, spells) = find_min_mana(game.clone(), false).unwrap();
+        assert_eq!(mana, 641);
+
+        let mut replay = game.clone();
+        for spell in spells {
+            replay.play_wizard_turn(spell);
+            if replay.still_playing() {
+                replay.play_boss_turn();
+            }
+        }
+        assert!(!replay.still_playing());
+        assert!(replay.is_wizard_winner());
+        assert_eq!(replay.wizard.spent_mana, mana);
+
+        assert!(find_min_mana(game, true).is_none());
+    }
 }
@@ -1,109 +1,93 @@
 #![allow(warnings, unused)]
 
-use rand::{rngs::ThreadRng, seq::SliceRandom};
+use itertools::Itertools;
 
-const INPUT: [usize; 29] = [
-    1, 2, 3, 5, 7, 13, 17, 19, 23, 29, 31, 37, 41, 43, 53, 59, 61, 67, 71, 73, 79, 83, 89, 97, 101,
-    103, 107, 109, 113,
-];
+use crate::utls::read_text_from_file;
 
-struct Solution {
-    count: usize,
-    qe: usize,
+fn fetch_input() -> Vec<usize> {
+    read_text_from_file("15", "24")
+        .lines()
+        .map(|line| line.trim().parse().unwrap())
+        .collect()
 }
 
-impl Solution {
-    fn new(count: usize, qe: usize) -> Self {
-        Self { count, qe }
-    }
-}
+/// Checks if the packages can be filled into `gr_count` groups with the same target weight.
+fn can_split(packages: &[usize], gr_count: usize, target: usize) -> bool {
+    fn fill(packages: &[usize], groups: &mut [usize], target: usize) -> bool {
+        let Some((&weight, rest)) = packages.split_first() else {
+            return groups.iter().all(|&sum| sum == target);
+        };
 
-fn get_random_solution_3(
-    mut packages: Vec<usize>,
-    target_weight: usize,
-    rng: &mut ThreadRng,
-) -> Option<Solution> {
-    packages.shuffle(rng);
-
-    let mut group_1 = vec![];
-    let mut gr_1_sum = 0;
-    let mut gr_2_sum = 0;
-    let mut gr_3_sum = 0;
-
-    while let Some(num) = packages.pop() {
-        match num {
-            weight if weight + gr_1_sum <= target_weight => {
-                gr_1_sum += weight;
-                group_1.push(weight);
+        for idx in 0..groups.len() {
+            if groups[idx] + weight > target {
+                continue;
+            }
+            // Groups with the same sum are interchangeable.
+            if groups[..idx].contains(&groups[idx]) {
+                continue;
             }
-            weight if weight + gr_2_sum <= target_weight => gr_2_sum += weight,
-            weight if weight + gr_3_sum <= target_weight => gr_3_sum += weight,
-            _ => return None,
-        }
-    }
-
-    Some(Solution::new(group_1.len(), group_1.iter().product()))
-}
 
-fn get_random_solution_4(
-    mut packages: Vec<usize>,
-    target_weight: usize,
-    rng: &mut ThreadRng,
-) -> Option<Solution> {
-    packages.shuffle(rng);
-
-    let mut group_1 = vec![];
-    let mut gr_1_sum = 0;
-    let mut gr_2_sum = 0;
-    let mut gr_3_sum = 0;
-    let mut gr_4_sum = 0;
-
-    while let Some(num) = packages.pop() {
-        match num {
-            weight if weight + gr_1_sum <= target_weight => {
-                gr_1_sum += weight;
-                group_1.push(weight);
+            groups[idx] += weight;
+            if fill(rest, groups, target) {
+                return true;
             }
-            weight if weight + gr_2_sum <= target_weight => gr_2_sum += weight,
-            weight if weight + gr_3_sum <= target_weight => gr_3_sum += weight,
-            weight if weight + gr_4_sum <= target_weight => gr_4_sum += weight,
-            _ => return None,
+            groups[idx] -= weight;
         }
+
+        false
     }
 
-    Some(Solution::new(group_1.len(), group_1.iter().product()))
+    if packages.iter().sum::<usize>() != gr_count * target {
+        return false;
+    }
+
+    let mut packages = packages.to_vec();
+    packages.sort_unstable_by(|a, b| b.cmp(a));
+
+    fill(&packages, &mut vec![0; gr_count], target)
 }
 
-fn get_qe_ideal_conf(packages: Vec<usize>, gr_count: usize) -> usize {
-    let mut min_count = usize::MAX;
-    let mut min_qe = usize::MAX;
-
-    let mut rng = rand::thread_rng();
-    let target = packages.iter().sum::<usize>() / gr_count;
-    let func = if gr_count == 3 {
-        get_random_solution_3
-    } else {
-        get_random_solution_4
-    };
-    for _ in 0..1000_000 {
-        if let Some(solution) = func(packages.clone(), target, &mut rng) {
-            if solution.count <= min_count {
-                min_count = solution.count;
-                min_qe = min_qe.min(solution.qe);
+/// Quantum entanglement is the product of the weights, which can overflow `usize` for large groups.
+fn get_qe_ideal_conf(packages: Vec<usize>, gr_count: usize) -> u128 {
+    let total: usize = packages.iter().sum();
+    assert_eq!(total % gr_count, 0, "packages can't be split equally");
+    let target = total / gr_count;
+
+    // Groups are built from indices since packages with the same weight are separate packages.
+    for size in 1..=packages.len() {
+        let candidates = (0..packages.len())
+            .combinations(size)
+            .filter(|group| group.iter().map(|&idx| packages[idx]).sum::<usize>() == target)
+            .map(|group| {
+                let qe = group
+                    .iter()
+                    .try_fold(1_u128, |qe, &idx| qe.checked_mul(packages[idx] as u128))
+                    .expect("quantum entanglement overflows u128");
+                (qe, group)
+            })
+            .sorted_unstable_by_key(|(qe, _)| *qe);
+
+        for (qe, group_1) in candidates {
+            let rest: Vec<_> = (0..packages.len())
+                .filter(|idx| !group_1.contains(idx))
+                .map(|idx| packages[idx])
+                .collect();
+            if can_split(&rest, gr_count - 1, target) {
+                return qe;
             }
         }
     }
 
-    min_qe
+    panic!("no valid configuration found")
 }
 
 fn part_1() {
-    let qe = get_qe_ideal_conf(INPUT.to_vec(), 3);
+    let qe = get_qe_ideal_conf(fetch_input(), 3);
 
     println!("part 1: qe of ideal config is {qe}");
 }
 fn part_2() {
-    let qe = get_qe_ideal_conf(INPUT.to_vec(), 4);
+    let qe = get_qe_ideal_conf(fetch_input(), 4);
 
     println!("part 2: qe of ideal config is {qe}");
 }
@@ -121,4 +105,24 @@ mod tests {
     fn get_test_packages() -> Vec<usize> {
         vec![1, 2, 3, 4, 5, 7, 8, 9, 10, 11]
     }
+
+    #[test]
+    fn test_get_qe_ideal_conf() {
+        assert_eq!(get_qe_ideal_conf(get_test_packages(), 3), 99);
+        assert_eq!(get_qe_ideal_conf(get_test_packages(), 4), 44);
+
+        // The repeated weights are separate packages, so taking two of the fives leaves the
+        // other two for the remaining groups.
+        assert_eq!(get_qe_ideal_conf(vec![5, 5, 5, 5, 1, 4, 3, 2], 3), 25);
+
+        let heavy = vec![1 << 40, 1 << 40, 1 << 40, 1 << 40];
+        assert_eq!(get_qe_ideal_conf(heavy, 2), 1 << 80);
+    }
+
+    #[test]
+    fn test_can_split() {
+        assert!(can_split(&[1, 2, 3, 4, 5, 7], 2, 11));
+        assert!(!can_split(&[1, 2, 3, 4, 5, 7], 3, 11));
+        assert!(!can_split(&[6, 6, 6, 4], 2, 11));
+    }
 }
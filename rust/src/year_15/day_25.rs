@@ -2,9 +2,19 @@
 
 use std::collections::HashMap;
 
+use crate::utls::read_text_from_file;
+
 const START_VALUE: usize = 20151125;
-const TARGET_ROW: usize = 3010;
-const TARGET_COL: usize = 3019;
+
+/// Parses "... Enter the code at row 3010, column 3019." into (row, column)
+fn parse_target(input: &str) -> (usize, usize) {
+    let mut nums = input
+        .split(|ch: char| !ch.is_ascii_digit())
+        .filter(|part| !part.is_empty())
+        .map(|num| num.parse().unwrap());
+
+    (nums.next().unwrap(), nums.next().unwrap())
+}
 
 fn get_next_value(current: usize) -> usize {
     (current * 252533) % 33554393
@@ -27,7 +37,8 @@ fn find_value_by(row: usize, col: usize) -> usize {
 }
 
 fn part_1() {
-    let code = find_value_by(TARGET_ROW, TARGET_COL);
+    let (row, col) = parse_target(&read_text_from_file("15", "25"));
+    let code = find_value_by(row, col);
 
     println!("part_1: Code is {code}");
 }
@@ -47,6 +58,12 @@ mod tests {
         assert_eq!(get_next_value(31916031), 18749137);
     }
 
+    #[test]
+    fn test_parse_target() {
+        let input = "To continue, please consult the code grid in the manual.  Enter the code at row 3010, column 3019.";
+        assert_eq!(parse_target(input), (3010, 3019));
+    }
+
     #[test]
     fn test_find_value_by() {
         assert_eq!(find_value_by(2, 1), 31916031);
@@ -5,7 +5,10 @@ use std::{
     io::{self, BufRead},
 };
 
-use crate::utls::read_lines_from_file;
+use crate::{
+    image::{self, Image},
+    utls::read_lines_from_file,
+};
 
 #[derive(Debug)]
 struct LightsGrid<T> {
@@ -126,6 +129,10 @@ fn run_bools() {
     let on_lights = grid.get_all_cells().iter().filter(|&&&cell| cell).count();
 
     println!("number of lit lights are {on_lights}");
+
+    image::export("15_06_lights", || {
+        Image::from_grid(&grid.cells, |on| image::binary(*on))
+    });
 }
 
 fn run_nums() {
@@ -141,6 +148,16 @@ fn run_nums() {
     let total_brightness: i32 = grid.get_all_cells().iter().map(|&&cell| cell).sum();
 
     println!("Total brightness is: {total_brightness}");
+
+    image::export("15_06_brightness", || {
+        let max = grid
+            .get_all_cells()
+            .into_iter()
+            .max()
+            .copied()
+            .unwrap_or_default();
+        Image::from_grid(&grid.cells, |value| image::heat(*value as u64, max as u64))
+    });
 }
 
 pub fn run() {
@@ -1,5 +1,8 @@
+use std::collections::{HashSet, VecDeque};
+
 use crate::utls::read_text_from_file;
-use rand::{Rng, rngs::ThreadRng, seq::SliceRandom};
+
+const FLOORS: u8 = 4;
 
 #[derive(Debug, Eq, PartialEq, Clone, Copy)]
 enum ItemType {
@@ -24,258 +27,130 @@ impl Item {
     }
 }
 
-#[derive(Debug, Clone, Eq, PartialEq)]
-enum Direction {
-    Up,
-    Down,
+/// The elevator floor and the floors of each generator/microchip pair, all starting from zero.
+/// Pairs are kept sorted because swapping the names of two pairs doesn't change the puzzle.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct State {
+    elevator: u8,
+    pairs: Vec<(u8, u8)>,
 }
 
-impl Direction {
-    fn to_int(&self) -> isize {
-        match self {
-            Direction::Up => 1,
-            Direction::Down => -1,
-        }
+impl State {
+    fn new(elevator: u8, mut pairs: Vec<(u8, u8)>) -> Self {
+        pairs.sort_unstable();
+        Self { elevator, pairs }
     }
 
-    fn rand(rng: &mut ThreadRng) -> Direction {
-        if rng.random::<bool>() {
-            Direction::Up
-        } else {
-            Direction::Down
-        }
-    }
-
-    fn rand_ratio(rng: &mut ThreadRng, ratio: f64) -> Direction {
-        if rng.random_bool(ratio) {
-            Direction::Up
-        } else {
-            Direction::Down
-        }
-    }
-}
-
-#[derive(Clone)]
-struct Move {
-    items: [Option<(String, ItemType)>; 2],
-    dir: Direction,
-}
-
-struct Game {
-    items: Vec<Item>,
-    cur_floor: isize,
-}
+    fn from_items(items: &[Item]) -> Self {
+        let pairs = items
+            .iter()
+            .filter(|item| item.item_type == ItemType::Generator)
+            .map(|generator| {
+                let chip = items
+                    .iter()
+                    .find(|item| {
+                        item.item_type == ItemType::Microchip && item.name == generator.name
+                    })
+                    .unwrap_or_else(|| panic!("no microchip for {}", generator.name));
+                (generator.floor as u8 - 1, chip.floor as u8 - 1)
+            })
+            .collect();
 
-impl Game {
-    fn new(items: Vec<Item>) -> Self {
-        Self {
-            items,
-            cur_floor: 1,
-        }
+        Self::new(0, pairs)
     }
 
-    fn apply_move(&mut self, mov: Move) {
-        let new_floor = self.cur_floor + mov.dir.to_int();
-        assert!(new_floor > 0);
-        assert!(new_floor <= 4);
-
-        let items: Vec<_> = mov.items.into_iter().flatten().collect();
-        assert!(!items.is_empty());
-        assert!(items.len() <= 2);
-
-        items.into_iter().for_each(|(name, typ)| {
-            let item = self
-                .items
-                .iter_mut()
-                .find(|item| item.name == name && item.item_type == typ)
-                .unwrap();
-            assert!(item.floor == self.cur_floor);
-            item.floor = new_floor;
-        });
-
-        self.cur_floor = new_floor;
+    fn is_done(&self) -> bool {
+        self.pairs
+            .iter()
+            .all(|&(generator, chip)| generator == FLOORS - 1 && chip == FLOORS - 1)
     }
 
-    fn success(&self) -> bool {
-        self.items.iter().all(|item| item.floor == 4)
+    /// A microchip gets fried when it shares a floor with a foreign generator and its own
+    /// generator is somewhere else.
+    fn is_valid(&self) -> bool {
+        self.pairs.iter().all(|&(generator, chip)| {
+            generator == chip || self.pairs.iter().all(|&(other, _)| other != chip)
+        })
     }
 
-    fn has_lost(&self) -> bool {
-        for chip in self
-            .items
-            .iter()
-            .filter(|t| t.item_type == ItemType::Microchip)
-        {
-            let match_gen = self
-                .items
-                .iter()
-                .find(|t| t.item_type == ItemType::Generator && t.name == chip.name)
-                .unwrap();
-            if match_gen.floor != chip.floor
-                && self
-                    .items
-                    .iter()
-                    .filter(|item| item.item_type == ItemType::Generator)
-                    .any(|g| g.floor == chip.floor)
-            {
-                return true;
-            }
+    fn set_floor(pairs: &mut [(u8, u8)], slot: usize, floor: u8) {
+        let pair = &mut pairs[slot / 2];
+        if slot.is_multiple_of(2) {
+            pair.0 = floor;
+        } else {
+            pair.1 = floor;
         }
-
-        false
     }
 
-    fn create_rand_move(&self, rng: &mut ThreadRng) -> Option<Move> {
-        let items: Vec<_> = self
-            .items
-            .iter()
-            .filter(|item| item.floor == self.cur_floor)
-            .map(|item| (item.name.clone(), item.item_type))
-            .collect();
-
-        let mut gens = vec![];
-        let micros: Vec<_> = items
+    fn next_states(&self) -> Vec<State> {
+        // Even slots are generators and odd slots are microchips.
+        let slots: Vec<_> = self
+            .pairs
             .iter()
-            .filter(|(name, typ)| {
-                if *typ == ItemType::Microchip {
-                    true
-                } else {
-                    gens.push((name.to_owned(), *typ));
-                    false
-                }
-            })
-            .cloned()
+            .flat_map(|&(generator, chip)| [generator, chip])
+            .enumerate()
+            .filter(|(_, floor)| *floor == self.elevator)
+            .map(|(slot, _)| slot)
             .collect();
 
-        let mut counter = 0;
-
-        while counter < 15 {
-            let mut cloned_items = items.clone();
-            cloned_items.shuffle(rng);
-
-            //optimize direction
-            let dir = if self.items.iter().all(|item| item.floor >= self.cur_floor) {
-                Direction::Up
-            } else {
-                let mut dir = if counter < 1 {
-                    let cur_count = items.len();
-                    let prev_count = self
-                        .items
-                        .iter()
-                        .filter(|i| i.floor == self.cur_floor - 1)
-                        .count();
-                    if prev_count > cur_count {
-                        Direction::rand_ratio(rng, 0.1)
-                    } else {
-                        let next_count = self
-                            .items
-                            .iter()
-                            .filter(|i| i.floor == self.cur_floor + 1)
-                            .count();
-                        if next_count < cur_count {
-                            Direction::rand_ratio(rng, 0.9)
-                        } else {
-                            Direction::rand(rng)
-                        }
-                    }
-                } else {
-                    Direction::rand(rng)
-                };
-
-                let next_floor = self.cur_floor + dir.to_int();
-                if !next_floor.is_positive() || next_floor > 4 {
-                    dir = match dir {
-                        Direction::Up => Direction::Down,
-                        Direction::Down => Direction::Up,
-                    };
-                }
+        let mut carries: Vec<Vec<usize>> = slots.iter().map(|&slot| vec![slot]).collect();
+        for (idx, &first) in slots.iter().enumerate() {
+            for &second in &slots[idx + 1..] {
+                carries.push(vec![first, second]);
+            }
+        }
 
-                dir
-            };
+        let floors = [self.elevator.checked_sub(1), Some(self.elevator + 1)];
 
-            //optimize items;
-            let final_items = if dir == Direction::Up {
-                match counter {
-                    c if c < 2 && micros.len() > 1 => {
-                        let mut cloned = micros.clone();
-                        cloned.shuffle(rng);
-
-                        [cloned.pop(), cloned.pop()]
-                    }
-                    c if c < 4 && gens.len() > 1 => {
-                        let mut cloned = gens.clone();
-                        cloned.shuffle(rng);
-
-                        [cloned.pop(), cloned.pop()]
-                    }
-                    c if c < 8 => [cloned_items.pop(), cloned_items.pop()],
-                    _ => [cloned_items.pop(), None],
+        let mut states = Vec::new();
+        for next_floor in floors.into_iter().flatten().filter(|f| *f < FLOORS) {
+            for carry in &carries {
+                let mut pairs = self.pairs.clone();
+                for &slot in carry {
+                    Self::set_floor(&mut pairs, slot, next_floor);
                 }
-            } else if counter < 8 {
-                [cloned_items.pop(), None]
-            } else {
-                [cloned_items.pop(), cloned_items.pop()]
-            };
-
-            let mv = Move {
-                items: final_items,
-                dir,
-            };
 
-            if !self.check_wrong_mv(mv.clone()) {
-                return Some(mv);
+                let state = State::new(next_floor, pairs);
+                if state.is_valid() {
+                    states.push(state);
+                }
             }
-
-            counter += 1;
         }
 
-        None
+        states
     }
+}
 
-    fn check_wrong_mv(&self, mv: Move) -> bool {
-        let next_floor = self.cur_floor + mv.dir.to_int();
-        let mut pot_items: Vec<_> = self
-            .items
-            .iter()
-            .filter(|item| item.floor == self.cur_floor || item.floor == next_floor)
-            .cloned()
-            .collect();
+/// Breadth first search over the canonicalised states returning the minimum count of moves.
+fn min_moves(items: &[Item]) -> Option<usize> {
+    let start = State::from_items(items);
+    let mut visited = HashSet::from([start.clone()]);
+    let mut queue = VecDeque::from([(start, 0)]);
 
-        mv.items.into_iter().flatten().for_each(|(name, typ)| {
-            let item = pot_items
-                .iter_mut()
-                .find(|item| item.name == name && item.item_type == typ)
-                .unwrap();
-            item.floor = next_floor;
-        });
+    while let Some((state, moves)) = queue.pop_front() {
+        if state.is_done() {
+            return Some(moves);
+        }
 
-        for chip in pot_items
-            .iter()
-            .filter(|t| t.item_type == ItemType::Microchip)
-        {
-            let match_gen = pot_items
-                .iter()
-                .find(|t| t.item_type == ItemType::Generator && t.name == chip.name);
-
-            if (match_gen.is_none() || match_gen.unwrap().floor != chip.floor)
-                && pot_items
-                    .iter()
-                    .filter(|item| item.item_type == ItemType::Generator)
-                    .any(|g| g.floor == chip.floor)
-            {
-                return true;
+        for next in state.next_states() {
+            if visited.insert(next.clone()) {
+                queue.push_back((next, moves + 1));
             }
         }
-
-        false
     }
+
+    None
 }
 
-fn fetch_input() -> Vec<Item> {
+fn parse_input(input: &str) -> Vec<Item> {
     let mut items = vec![];
 
-    for (i, line) in read_text_from_file("16", "11").lines().take(3).enumerate() {
-        for part in line.split(',') {
+    for (i, line) in input.lines().take(FLOORS as usize - 1).enumerate() {
+        let parts = line
+            .split(',')
+            .flat_map(|part| part.split(" and "))
+            .filter(|part| part.contains("generator") || part.contains("microchip"));
+        for part in parts {
             let mut words = part.split_whitespace();
             let item_type = match words.next_back().unwrap() {
                 word if word.starts_with("gener") => ItemType::Generator,
@@ -294,29 +169,22 @@ fn fetch_input() -> Vec<Item> {
     items
 }
 
+fn fetch_input() -> Vec<Item> {
+    parse_input(&read_text_from_file("16", "11"))
+}
+
+/// Adds a generator and a microchip for each name on the first floor.
+fn add_pairs(items: &mut Vec<Item>, names: &[&str]) {
+    for name in names {
+        items.push(Item::new((*name).into(), 1, ItemType::Generator));
+        items.push(Item::new((*name).into(), 1, ItemType::Microchip));
+    }
+}
+
 fn part_1() {
     let items = fetch_input();
 
-    let mut min_move = usize::MAX;
-
-    let mut rng = rand::rng();
-    for _ in 0..1000 {
-        let mut game = Game::new(items.clone());
-        let mut count = 0;
-        while count < min_move && !game.has_lost() {
-            if let Some(mov) = game.create_rand_move(&mut rng) {
-                game.apply_move(mov);
-                count += 1;
-                if game.success() {
-                    println!("success {count}");
-                    min_move = min_move.min(count);
-                    break;
-                }
-            } else {
-                break;
-            }
-        }
-    }
+    let min_move = min_moves(&items).expect("no solution found");
 
     println!("part 1: min is {min_move}");
 }
@@ -324,31 +192,9 @@ fn part_1() {
 fn part_2() {
     let mut items = fetch_input();
 
-    items.push(Item::new("elerium".into(), 1, ItemType::Generator));
-    items.push(Item::new("elerium".into(), 1, ItemType::Microchip));
-    items.push(Item::new("dilithium".into(), 1, ItemType::Generator));
-    items.push(Item::new("dilithium".into(), 1, ItemType::Microchip));
-
-    let mut min_move = usize::MAX;
-
-    let mut rng = rand::rng();
-    for _ in 0..10000000 {
-        let mut game = Game::new(items.clone());
-        let mut count = 0;
-        while count < min_move && !game.has_lost() {
-            if let Some(mov) = game.create_rand_move(&mut rng) {
-                game.apply_move(mov);
-                count += 1;
-                if game.success() {
-                    println!("success {count}");
-                    min_move = min_move.min(count);
-                    break;
-                }
-            } else {
-                break;
-            }
-        }
-    }
+    add_pairs(&mut items, &["elerium", "dilithium"]);
+
+    let min_move = min_moves(&items).expect("no solution found");
 
     println!("part 2: min is {min_move}");
 }
@@ -357,3 +203,26 @@ pub fn run() {
     part_1();
     part_2();
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const INPUT: &str = "\
+The first floor contains a hydrogen-compatible microchip and a lithium-compatible microchip.
+The second floor contains a hydrogen generator.
+The third floor contains a lithium generator.
+The fourth floor contains nothing relevant.";
+
+    #[test]
+    fn test_min_moves() {
+        let items = parse_input(INPUT);
+        assert_eq!(items.len(), 4);
+        assert_eq!(min_moves(&items), Some(11));
+
+        let state = State::from_items(&items);
+        assert_eq!(state, State::new(0, vec![(2, 0), (1, 0)]));
+        assert!(state.is_valid());
+        assert!(!State::new(0, vec![(0, 1), (1, 1)]).is_valid());
+    }
+}
@@ -1,8 +1,10 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet, VecDeque};
 
-use rand::{rngs::ThreadRng, seq::IndexedRandom};
+use crate::utls::read_text_from_file;
 
-const FAV_NUM: i32 = 1362;
+fn fetch_fav_num() -> i32 {
+    read_text_from_file("16", "13").trim().parse().unwrap()
+}
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
 struct Pos {
@@ -21,144 +23,108 @@ impl Pos {
 
         num.count_ones() & 1 != 0
     }
-}
-
-struct Game {
-    cur_pos: Pos,
-    fav_num: i32,
-    pos_cache: HashSet<Pos>,
-    last_pos: Pos,
-}
-
-impl Game {
-    fn start(fav_num: i32) -> Game {
-        let start_pos = Pos::new(1, 1);
-        Game {
-            cur_pos: start_pos,
-            fav_num,
-            pos_cache: HashSet::from([start_pos]),
-            last_pos: start_pos,
-        }
-    }
-
-    fn is_wall(&self, pos: &Pos) -> bool {
-        Pos::new(pos.x, pos.y).is_wall(self.fav_num)
-    }
 
-    fn is_pos_valid(&self, pos: &Pos) -> bool {
-        pos.x > -1
-            && pos.y > -1
-            && *pos != self.last_pos
-            && !self.is_wall(pos)
-            && !self.pos_cache.contains(pos)
+    fn neighbours(self) -> impl Iterator<Item = Pos> {
+        [(1, 0), (0, 1), (-1, 0), (0, -1)]
+            .into_iter()
+            .map(move |(dx, dy)| Pos::new(self.x + dx, self.y + dy))
+            .filter(|pos| pos.x > -1 && pos.y > -1)
     }
+}
 
-    fn next_possible_positions(&self) -> Vec<Pos> {
-        let mut positions = vec![];
-        let next_x = Pos::new(self.cur_pos.x + 1, self.cur_pos.y);
-        if self.is_pos_valid(&next_x) {
-            positions.push(next_x);
-        }
-        let next_y = Pos::new(self.cur_pos.x, self.cur_pos.y + 1);
-        if self.is_pos_valid(&next_y) {
-            positions.push(next_y);
-        }
+const START: Pos = Pos { x: 1, y: 1 };
 
-        let prev_x = Pos::new(self.cur_pos.x - 1, self.cur_pos.y);
-        if self.is_pos_valid(&prev_x) {
-            positions.push(prev_x);
-        }
-        let prev_y = Pos::new(self.cur_pos.x, self.cur_pos.y - 1);
-        if self.is_pos_valid(&prev_y) {
-            positions.push(prev_y);
-        }
-
-        positions
-    }
+struct Office {
+    seed: i32,
+}
 
-    fn move_to(&mut self, pos: Pos) {
-        self.last_pos = self.cur_pos;
-        self.cur_pos = pos;
-        self.pos_cache.insert(pos);
+impl Office {
+    fn new(seed: i32) -> Self {
+        Self { seed }
     }
 
-    fn play(&mut self, target: Pos, min_moves: usize, rng: &mut ThreadRng) -> Option<usize> {
-        let in_target_direction = |pos: &Pos, cur: &Pos| {
-            (cur.x - target.x * cur.x - pos.x).is_positive()
-                || (cur.y - target.y * cur.y - pos.y).is_positive()
-        };
+    /// Breadth first search from start returning the distances of all positions reached within
+    /// the steps limit. The search stops early once the target is reached.
+    fn explore(&self, start: Pos, max_steps: usize, target: Option<Pos>) -> HashMap<Pos, usize> {
+        let mut distances = HashMap::from([(start, 0)]);
+        let mut queue = VecDeque::from([start]);
 
-        while self.pos_cache.len() <= min_moves {
-            let possible_pos = self.next_possible_positions();
-            if possible_pos.is_empty() {
+        while let Some(pos) = queue.pop_front() {
+            let dist = distances[&pos];
+            if Some(pos) == target {
                 break;
             }
-
-            let mut next_pos = possible_pos.choose(rng).unwrap().to_owned();
-            if possible_pos.len() > 1 && !in_target_direction(&next_pos, &self.cur_pos) {
-                next_pos = possible_pos.choose(rng).unwrap().to_owned();
+            if dist == max_steps {
+                continue;
             }
 
-            self.move_to(next_pos);
-
-            if self.cur_pos == target {
-                return Some(self.pos_cache.len() - 1);
+            for next in pos.neighbours() {
+                if next.is_wall(self.seed) || distances.contains_key(&next) {
+                    continue;
+                }
+                distances.insert(next, dist + 1);
+                queue.push_back(next);
             }
         }
 
-        None
+        distances
     }
 
-    fn get_locations(mut self, steps: usize, rng: &mut ThreadRng) -> HashSet<Pos> {
-        for _ in 0..steps {
-            let possible_pos = self.next_possible_positions();
-            if possible_pos.is_empty() {
-                break;
-            }
+    fn shortest_path(&self, start: Pos, target: Pos, max_steps: usize) -> Option<usize> {
+        self.explore(start, max_steps, Some(target))
+            .get(&target)
+            .copied()
+    }
 
-            let next_pos = *possible_pos.choose(rng).unwrap();
+    fn reachable_within(&self, start: Pos, steps: usize) -> HashSet<Pos> {
+        self.explore(start, steps, None).into_keys().collect()
+    }
 
-            self.move_to(next_pos);
+    /// Renders the top left part of the office marking the explored positions with 'O'.
+    fn render(&self, explored: &HashMap<Pos, usize>, width: i32, height: i32) -> String {
+        let mut out = String::new();
+        for y in 0..height {
+            for x in 0..width {
+                let pos = Pos::new(x, y);
+                let ch = if explored.contains_key(&pos) {
+                    'O'
+                } else if pos.is_wall(self.seed) {
+                    '#'
+                } else {
+                    '.'
+                };
+                out.push(ch);
+            }
+            out.push('\n');
         }
 
-        self.pos_cache
+        out
     }
 }
 
-fn get_best_route(target: Pos, seed: i32) -> usize {
-    let mut min_moves = usize::MAX;
-
-    let mut rng = rand::rng();
+// The office is infinite, so the search needs a limit to stop on unreachable targets.
+const MAX_STEPS: usize = 10_000;
 
-    for _ in 0..1000000000 {
-        let mut game = Game::start(seed);
-        if let Some(moves) = game.play(target, min_moves, &mut rng) {
-            if min_moves == moves {
-                break;
-            }
-            min_moves = min_moves.min(moves);
-        }
-    }
-
-    min_moves
+#[allow(unused)]
+fn print_explored(seed: i32, steps: usize) {
+    let office = Office::new(seed);
+    let explored = office.explore(START, steps, None);
+    let size = steps as i32 + 2;
+    println!("{}", office.render(&explored, size, size));
 }
 
 fn part_1() {
-    let best_route = get_best_route(Pos::new(31, 39), FAV_NUM);
+    let best_route = Office::new(fetch_fav_num())
+        .shortest_path(START, Pos::new(31, 39), MAX_STEPS)
+        .expect("target isn't reachable");
 
     println!("part_1; best route is {best_route}");
 }
 
 fn part_2() {
-    let mut rng = rand::rng();
-
-    let mut all_locations = HashSet::new();
-    for _ in 0..1000000 {
-        let locations = Game::start(FAV_NUM).get_locations(50, &mut rng);
-        all_locations.extend(locations);
-    }
+    let locations = Office::new(fetch_fav_num()).reachable_within(START, 50);
 
-    println!("locations are {}", all_locations.len());
+    println!("locations are {}", locations.len());
 }
 
 pub fn run() {
@@ -173,16 +139,36 @@ mod tests {
 
     #[test]
     fn test_is_wall() {
-        let game = Game::start(10);
-        assert!(!game.is_wall(&Pos::new(0, 0)));
-        assert!(game.is_wall(&Pos::new(1, 0)));
-        assert!(!game.is_wall(&Pos::new(0, 1)));
-        assert!(game.is_wall(&Pos::new(6, 2)));
-        assert!(game.is_wall(&Pos::new(9, 6)));
+        assert!(!Pos::new(0, 0).is_wall(10));
+        assert!(Pos::new(1, 0).is_wall(10));
+        assert!(!Pos::new(0, 1).is_wall(10));
+        assert!(Pos::new(6, 2).is_wall(10));
+        assert!(Pos::new(9, 6).is_wall(10));
     }
 
     #[test]
     fn test_game() {
-        assert_eq!(get_best_route(Pos::new(7, 4), 10), 11);
+        let office = Office::new(10);
+        assert_eq!(
+            office.shortest_path(START, Pos::new(7, 4), MAX_STEPS),
+            Some(11)
+        );
+        assert_eq!(office.shortest_path(START, Pos::new(7, 4), 10), None);
+
+        let reachable = office.reachable_within(START, 2);
+        assert_eq!(reachable.len(), 5);
+        assert!(reachable.contains(&Pos::new(0, 0)));
+    }
+
+    #[test]
+    fn test_render() {
+        let office = Office::new(10);
+        let explored = office.explore(START, 1, None);
+        let expected = "\
+.#.#
+OO#.
+#O..
+";
+        assert_eq!(office.render(&explored, 4, 3), expected);
     }
 }
@@ -1,52 +1,53 @@
 use std::collections::VecDeque;
 
-#[derive(Debug)]
-struct Elf {
-    id: usize,
-    presents: usize,
-}
+use crate::utls::read_text_from_file;
 
-impl Elf {
-    fn new(id: usize, presents: usize) -> Self {
-        Self { id, presents }
-    }
+fn fetch_elves_count() -> usize {
+    read_text_from_file("16", "19").trim().parse().unwrap()
 }
 
-fn part_1() {
-    let mut elves = create_elves(3014603);
-    // let mut elves = create_elves(5);
-    while elves.len() > 1 {
-        for pair in elves.chunks_mut(2) {
-            if pair.len() == 2 {
-                pair[0].presents += pair[1].presents;
-                pair[1].presents = 0;
-            }
-        }
+/// Winner when each elf steals from the elf on the left, which is Josephus with every second
+/// elf removed: writing n = 2^m + l gives the winner 2l + 1.
+fn winner_left(elves: usize) -> usize {
+    assert!(elves > 0);
+    let power = 1 << elves.ilog2();
 
-        let len = elves.len();
-        if is_odd(len) {
-            elves[len - 1].presents += elves[0].presents;
-            elves[0].presents = 0;
-        }
+    2 * (elves - power) + 1
+}
 
-        elves.retain(|num| num.presents != 0);
-    }
+/// Winner when each elf steals from the elf across the circle. With p being the largest power of
+/// three not greater than n, the winner grows by one up to 2p and by two after it.
+fn winner_across(elves: usize) -> usize {
+    assert!(elves > 0);
+    let power = 3_usize.pow(elves.ilog(3));
 
-    println!("part_1: lucky elf is {} ", elves[0].id);
+    match elves - power {
+        0 => elves,
+        rest if rest <= power => rest,
+        _ => 2 * elves - 3 * power,
+    }
 }
 
-fn create_elves(size: usize) -> Vec<Elf> {
-    (0..size).map(|i| Elf::new(i + 1, 1)).collect()
-}
+/// Simulates the circle removing every k-th elf, so `simulate_steps(n, 2)` matches `winner_left`.
+#[allow(unused)]
+fn simulate_steps(elves: usize, step: usize) -> usize {
+    assert!(elves > 0 && step > 0);
+    let mut circle: VecDeque<_> = (1..=elves).collect();
+
+    while circle.len() > 1 {
+        circle.rotate_left((step - 1) % circle.len());
+        circle.pop_front();
+    }
 
-fn is_odd(num: usize) -> bool {
-    num & 1 != 0
+    circle[0]
 }
 
-fn part_2() {
-    let input = 3014603;
-    let mut left: VecDeque<i32> = (1..input / 2 + 1).collect();
-    let mut right: VecDeque<i32> = (input / 2 + 1..input + 1).rev().collect();
+/// Simulates stealing from across with two halves of the circle.
+#[allow(unused)]
+fn simulate_across(elves: usize) -> usize {
+    assert!(elves > 0);
+    let mut left: VecDeque<_> = (1..elves / 2 + 1).collect();
+    let mut right: VecDeque<_> = (elves / 2 + 1..elves + 1).rev().collect();
 
     while !left.is_empty() && !right.is_empty() {
         if left.len() > right.len() {
@@ -59,7 +60,17 @@ fn part_2() {
         left.push_back(right.pop_back().unwrap());
     }
 
-    let winner = left.front().unwrap_or_else(|| right.front().unwrap());
+    *left.front().unwrap_or_else(|| right.front().unwrap())
+}
+
+fn part_1() {
+    let winner = winner_left(fetch_elves_count());
+
+    println!("part_1: lucky elf is {winner} ");
+}
+
+fn part_2() {
+    let winner = winner_across(fetch_elves_count());
 
     println!("part_2: lucky elf is {winner}");
 }
@@ -68,3 +79,27 @@ pub fn run() {
     part_1();
     part_2();
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_example() {
+        assert_eq!(winner_left(5), 3);
+        assert_eq!(winner_across(5), 2);
+        assert_eq!(simulate_steps(5, 2), 3);
+        assert_eq!(simulate_across(5), 2);
+    }
+
+    #[test]
+    fn test_closed_forms() {
+        for elves in 1..500 {
+            assert_eq!(winner_left(elves), simulate_steps(elves, 2), "{elves}");
+            assert_eq!(winner_across(elves), simulate_across(elves), "{elves}");
+        }
+
+        assert_eq!(simulate_steps(7, 3), 4);
+        assert_eq!(simulate_steps(1, 3), 1);
+    }
+}
@@ -1,4 +1,4 @@
-use std::collections::HashSet;
+use std::collections::{HashSet, VecDeque};
 
 use crate::utls::read_text_from_file;
 
@@ -32,30 +32,126 @@ impl Node {
             avail,
         }
     }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Cell {
+    Empty,
+    Movable,
+    Wall,
+}
+
+type Pos = (usize, usize);
+
+struct Grid {
+    cells: Vec<Vec<Cell>>,
+    empty: Pos,
+}
+
+impl Grid {
+    /// Nodes holding more data than the empty node can take will never move, so they are walls.
+    fn new(nodes: &[Node]) -> Self {
+        let empty_node = nodes
+            .iter()
+            .find(|node| node.used == 0)
+            .expect("no empty node");
+        let max_x = nodes.iter().map(|node| node.x).max().unwrap();
+        let max_y = nodes.iter().map(|node| node.y).max().unwrap();
+
+        let mut cells = vec![vec![Cell::Wall; max_x + 1]; max_y + 1];
+        for node in nodes {
+            cells[node.y][node.x] = match node.used {
+                0 => Cell::Empty,
+                used if used > empty_node.size => Cell::Wall,
+                _ => Cell::Movable,
+            };
+        }
+
+        Self {
+            cells,
+            empty: (empty_node.x, empty_node.y),
+        }
+    }
 
-    fn get_symbol(&self, max_x: usize) -> char {
-        match (self.x, self.y) {
-            (0, 0) => 'S',
-            (x, 0) if x == max_x => 'G',
-            _ => {
-                if self.used == 0 {
-                    '_'
-                } else if self.size > 100 {
-                    '#'
-                } else {
-                    '.'
+    fn width(&self) -> usize {
+        self.cells[0].len()
+    }
+
+    fn neighbours(&self, (x, y): Pos) -> impl Iterator<Item = Pos> + '_ {
+        [
+            x.checked_sub(1).map(|x| (x, y)),
+            Some((x + 1, y)),
+            y.checked_sub(1).map(|y| (x, y)),
+            Some((x, y + 1)),
+        ]
+        .into_iter()
+        .flatten()
+        .filter(|&(x, y)| {
+            self.cells
+                .get(y)
+                .and_then(|row| row.get(x))
+                .is_some_and(|cell| *cell != Cell::Wall)
+        })
+    }
+
+    fn index(&self, (x, y): Pos) -> usize {
+        y * self.width() + x
+    }
+
+    /// Breadth first search over the positions of the empty node and the goal data, moving the
+    /// goal data from the top right corner to the top left one. Each move swaps the empty node
+    /// with a neighbour, which moves the goal data when the neighbour holds it.
+    fn count_moves(&self) -> Option<usize> {
+        let start = (self.empty, (self.width() - 1, 0));
+        let cells_count = self.width() * self.cells.len();
+        let mut visited = vec![false; cells_count * cells_count];
+        visited[self.index(start.0) * cells_count + self.index(start.1)] = true;
+        let mut queue = VecDeque::from([(start, 0)]);
+
+        while let Some(((empty, goal), moves)) = queue.pop_front() {
+            if goal == (0, 0) {
+                return Some(moves);
+            }
+
+            for next_empty in self.neighbours(empty) {
+                let next_goal = if next_empty == goal { empty } else { goal };
+                let idx = self.index(next_empty) * cells_count + self.index(next_goal);
+                if !visited[idx] {
+                    visited[idx] = true;
+                    queue.push_back(((next_empty, next_goal), moves + 1));
                 }
             }
         }
+
+        None
     }
+
+    #[allow(unused)]
+    fn print(&self) {
+        let max_x = self.width() - 1;
+        for (y, row) in self.cells.iter().enumerate() {
+            let line: String = row
+                .iter()
+                .enumerate()
+                .map(|(x, cell)| match ((x, y), cell) {
+                    ((0, 0), _) => 'S',
+                    ((x, 0), _) if x == max_x => 'G',
+                    (_, Cell::Empty) => '_',
+                    (_, Cell::Wall) => '#',
+                    (_, Cell::Movable) => '.',
+                })
+                .collect();
+            println!("{line}");
+        }
+    }
+}
+
+fn parse_nodes(input: &str) -> Vec<Node> {
+    input.lines().skip(2).map(Node::from_line).collect()
 }
 
 fn fetch_nodes() -> Vec<Node> {
-    read_text_from_file("16", "22")
-        .lines()
-        .skip(2)
-        .map(Node::from_line)
-        .collect()
+    parse_nodes(&read_text_from_file("16", "22"))
 }
 
 fn part_1() {
@@ -74,50 +170,8 @@ fn part_1() {
 }
 
 fn part_2() {
-    let nodes = fetch_nodes();
-    let max_x = nodes.iter().map(|node| node.x).max().unwrap();
-    let max_y = nodes.iter().map(|node| node.y).max().unwrap();
-
-    let mut grid = vec![vec![' '; max_x + 1]; max_y + 1];
-
-    for node in nodes.iter() {
-        grid[node.y][node.x] = node.get_symbol(max_x);
-    }
-
-    for line in grid {
-        println!("{}", line.iter().collect::<String>());
-    }
-
-    //    S....................................G
-    //    ......................................
-    //    ......................................
-    //    ......................................
-    //    ......................................
-    //    ......................................
-    //    ......................................
-    //    ......................................
-    //    ......................................
-    //    ......................................
-    //    ......................................
-    //    ......................................
-    //    ......................................
-    //    ......................................
-    //    ......................................
-    //    ......................................
-    //    ......................................
-    //    ......................................
-    //    ......................................
-    //    ..............................########
-    //    ......................................
-    //    ..................................._..
-    //    ......................................
-    //    ......................................
-
-    let empty_node = fetch_nodes()
-        .into_iter()
-        .find(|node| node.used == 0)
-        .unwrap();
-    let moves = 6 + empty_node.y + 8 + (max_x - 1) * 5;
+    let grid = Grid::new(&fetch_nodes());
+    let moves = grid.count_moves().expect("goal data can't be moved");
 
     println!("moves count from grid: {moves}");
 }
@@ -126,3 +180,59 @@ pub fn run() {
     part_1();
     part_2();
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const INPUT: &str = "\
+root@ebhq-gridcenter# df -h
+Filesystem            Size  Used  Avail  Use%
+/dev/grid/node-x0-y0   10T    8T     2T   80%
+/dev/grid/node-x0-y1   11T    6T     5T   54%
+/dev/grid/node-x0-y2   32T   28T     4T   87%
+/dev/grid/node-x1-y0    9T    7T     2T   77%
+/dev/grid/node-x1-y1    8T    0T     8T    0%
+/dev/grid/node-x1-y2   11T    7T     4T   63%
+/dev/grid/node-x2-y0   10T    6T     4T   60%
+/dev/grid/node-x2-y1    9T    8T     1T   88%
+/dev/grid/node-x2-y2    9T    6T     3T   66%";
+
+    #[test]
+    fn test_count_moves() {
+        let grid = Grid::new(&parse_nodes(INPUT));
+        assert_eq!(grid.cells[2][0], Cell::Wall);
+        assert_eq!(grid.empty, (1, 1));
+        assert_eq!(grid.count_moves(), Some(7));
+    }
+
+    /// Builds the `df` output for the layout with `_` as the empty node and `#` as walls.
+    fn build_input(layout: &str) -> String {
+        let mut input =
+            String::from("root@ebhq-gridcenter# df -h\nFilesystem Size Used Avail Use%\n");
+        for (y, line) in layout.lines().enumerate() {
+            for (x, ch) in line.chars().enumerate() {
+                let used = match ch {
+                    '_' => 0,
+                    '#' => 90,
+                    _ => 5,
+                };
+                let avail = 10 - used.min(10);
+                input.push_str(&format!(
+                    "/dev/grid/node-x{x}-y{y} 10T {used}T {avail}T 0%\n"
+                ));
+            }
+        }
+
+        input
+    }
+
+    #[test]
+    fn test_detour() {
+        // The goal data has to go around the wall. Walking the shortest goal path first and
+        // moving the empty node in front of it for each step takes 28 moves.
+        let grid = Grid::new(&parse_nodes(&build_input("..#..\n_....\n.....")));
+        assert_eq!(grid.cells[0][2], Cell::Wall);
+        assert_eq!(grid.count_moves(), Some(26));
+    }
+}
@@ -1,6 +1,9 @@
 use std::collections::{HashMap, HashSet};
 
-use crate::utls::read_text_from_file;
+use crate::{
+    dot::{self, Graph, Shape},
+    utls::read_text_from_file,
+};
 
 const TARGET_NAME: &str = "shiny gold";
 
@@ -39,6 +42,25 @@ fn parse_bags(input: &str) -> HashMap<&str, Vec<BagEntry<'_>>> {
     bags
 }
 
+/// Builds the graph from each bag to the bags it contains with their counts as labels.
+fn build_graph(bags: &HashMap<&str, Vec<BagEntry>>) -> Graph {
+    let mut graph = Graph::directed();
+    for (&bag, children) in bags {
+        let shape = match bag {
+            TARGET_NAME => Shape::DoubleCircle,
+            _ if children.is_empty() => Shape::Box,
+            _ => Shape::Ellipse,
+        };
+        graph.node(bag, shape);
+
+        for child in children {
+            graph.labelled_edge(bag, child.name, child.count);
+        }
+    }
+
+    graph
+}
+
 fn calc_contain_count(input: &str) -> usize {
     let bags = parse_bags(input);
 
@@ -91,6 +113,8 @@ fn part_1() {
     let answer = calc_contain_count(&input);
 
     println!("Part 1 answer is {answer}");
+
+    dot::export("20_07_bags", || build_graph(&parse_bags(&input)));
 }
 
 fn part_2() {
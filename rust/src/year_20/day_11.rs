@@ -1,4 +1,8 @@
-use crate::utls::read_text_from_file;
+use crate::{
+    anim,
+    automaton::{Automaton, DenseGrid, Neighbourhood, Rule, count_state},
+    utls::read_text_from_file,
+};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum State {
@@ -28,216 +32,75 @@ impl State {
     }
 }
 
-#[derive(Debug)]
-struct SeatLayout {
-    cells: Vec<Vec<State>>,
-}
+type SeatLayout = Automaton<State, DenseGrid<State>>;
 
-impl From<&str> for SeatLayout {
-    fn from(input: &str) -> Self {
-        let cells = input
-            .lines()
-            .map(|line| line.chars().map(State::from).collect())
-            .collect();
+fn parse_layout(input: &str, neighbourhood: Neighbourhood<State>, rule: Rule<State>) -> SeatLayout {
+    let cells = input
+        .lines()
+        .map(|line| line.chars().map(State::from).collect())
+        .collect();
 
-        SeatLayout { cells }
-    }
+    Automaton::new(
+        DenseGrid::from_rows(cells, State::Floor),
+        neighbourhood,
+        rule,
+    )
 }
 
-impl SeatLayout {
-    // It's easier for me the understand using indices
-    #[allow(clippy::needless_range_loop)]
-    fn apply_round_surround(&mut self) -> bool {
-        let mut changed = false;
-
-        let rows_count = self.cells.len();
-        let cols_count = self.cells[0].len();
-
-        let mut new_layout = vec![vec![State::Floor; cols_count]; rows_count];
-
-        for row in 0..rows_count {
-            for col in 0..cols_count {
-                new_layout[row][col] = match self.cells[row][col] {
-                    State::Empty => {
-                        let all_empty = (row.saturating_sub(1)..=row + 1)
-                            .flat_map(|r| (col.saturating_sub(1)..=col + 1).map(move |c| (r, c)))
-                            .all(|(r, c)| {
-                                let state =
-                                    self.cells.get(r).and_then(|row_slice| row_slice.get(c));
-
-                                !matches!(state, Some(State::Occupied))
-                            });
-                        if all_empty {
-                            changed = true;
-                            State::Occupied
-                        } else {
-                            State::Empty
-                        }
-                    }
-                    State::Occupied => {
-                        let occupied_count = (row.saturating_sub(1)..=row + 1)
-                            .flat_map(|r| (col.saturating_sub(1)..=col + 1).map(move |c| (r, c)))
-                            .filter(|&(r, c)| {
-                                self.cells.get(r).is_some_and(|row_slice| {
-                                    row_slice
-                                        .get(c)
-                                        .is_some_and(|&state| matches!(state, State::Occupied))
-                                })
-                            })
-                            .count();
-
-                        // Count the current cell too
-                        if occupied_count >= 5 {
-                            changed = true;
-                            State::Empty
-                        } else {
-                            State::Occupied
-                        }
-                    }
-                    State::Floor => State::Floor,
-                }
-            }
-        }
-
-        self.cells = new_layout;
-
-        changed
-    }
-
-    // It's easier for me the understand using indices
-    #[allow(clippy::needless_range_loop)]
-    fn apply_round_visible(&mut self) -> bool {
-        let mut changed = false;
-
-        let rows_count = self.cells.len();
-        let cols_count = self.cells[0].len();
-
-        let mut new_layout = vec![vec![State::Floor; cols_count]; rows_count];
-
-        const DELTAS: [(isize, isize); 8] = [
-            (-1, -1),
-            (-1, 0),
-            (-1, 1),
-            (0, -1),
-            (0, 1),
-            (1, -1),
-            (1, 0),
-            (1, 1),
-        ];
-
-        for row in 0..rows_count {
-            for col in 0..cols_count {
-                new_layout[row][col] = match self.cells[row][col] {
-                    State::Empty => {
-                        let all_empty = DELTAS
-                            .iter()
-                            .map(|(d_row, d_col)| self.find_first_seat(row, col, *d_row, *d_col))
-                            .all(|state| !matches!(state, Some(State::Occupied)));
-                        if all_empty {
-                            changed = true;
-                            State::Occupied
-                        } else {
-                            State::Empty
-                        }
-                    }
-                    State::Occupied => {
-                        let occupied_count = DELTAS
-                            .iter()
-                            .map(|(d_row, d_col)| self.find_first_seat(row, col, *d_row, *d_col))
-                            .filter(|state| matches!(state, Some(State::Occupied)))
-                            .count();
-
-                        if occupied_count >= 5 {
-                            changed = true;
-                            State::Empty
-                        } else {
-                            State::Occupied
-                        }
-                    }
-                    State::Floor => State::Floor,
-                }
-            }
-        }
-
-        self.cells = new_layout;
-
-        changed
+/// Seats get occupied when no neighbour is occupied and get empty when at least the given count
+/// of neighbours is occupied.
+fn seat_rule(state: State, neighbours: &[State], tolerance: usize) -> State {
+    match (state, count_state(neighbours, State::Occupied)) {
+        (State::Empty, 0) => State::Occupied,
+        (State::Occupied, occupied) if occupied >= tolerance => State::Empty,
+        _ => state,
     }
+}
 
-    fn find_first_seat(
-        &self,
-        row: usize,
-        col: usize,
-        delta_row: isize,
-        delta_col: isize,
-    ) -> Option<State> {
-        let mut row = row as isize;
-        let mut col = col as isize;
+fn surround_rule(state: State, neighbours: &[State]) -> State {
+    seat_rule(state, neighbours, 4)
+}
 
-        let rows_count = self.cells.len() as isize;
-        let cols_count = self.cells[0].len() as isize;
+fn visible_rule(state: State, neighbours: &[State]) -> State {
+    seat_rule(state, neighbours, 5)
+}
 
-        while (0..rows_count).contains(&row) && (0..cols_count).contains(&col) {
-            row += delta_row;
-            col += delta_col;
-            match self
-                .cells
-                .get(row as usize)
-                .and_then(|row_slice| row_slice.get(col as usize))
-            {
-                Some(State::Occupied) => return Some(State::Occupied),
-                Some(State::Empty) => return Some(State::Empty),
-                _ => {}
-            }
-        }
+fn render(layout: &SeatLayout) -> String {
+    layout
+        .storage()
+        .to_rows()
+        .iter()
+        .map(|row| {
+            let mut line: String = row.iter().map(State::get_char).collect();
+            line.push('\n');
+            line
+        })
+        .collect()
+}
 
-        None
-    }
+#[allow(dead_code)]
+fn print(layout: &SeatLayout) {
+    print!("{}", render(layout));
+}
 
-    #[allow(dead_code)]
-    fn print(&self) {
-        let lines: Vec<String> = self
-            .cells
-            .iter()
-            .map(|row| row.iter().map(State::get_char).collect::<String>())
-            .collect();
+fn calc_occupied(input: &str, neighbourhood: Neighbourhood<State>, rule: Rule<State>) -> usize {
+    let mut layout = parse_layout(input, neighbourhood, rule);
+    let counter = layout.run_until_stable(|layout| {
+        anim::frame(format_args!("Round {}", layout.steps()), || render(layout));
+    });
 
-        for line in lines {
-            println!("{line}");
-        }
-    }
+    println!("Rounds count is {counter}");
 
-    fn get_occupied_count(&self) -> usize {
-        self.cells
-            .iter()
-            .flatten()
-            .filter(|state| matches!(state, State::Occupied))
-            .count()
-    }
+    layout.count(State::Occupied)
 }
 
 fn calc_occupied_surround(input: &str) -> usize {
-    let mut layout = SeatLayout::from(input);
-    let mut counter = 0;
-    while layout.apply_round_surround() {
-        counter += 1;
-    }
-
-    println!("Rounds count is {counter}");
-
-    layout.get_occupied_count()
+    calc_occupied(input, Neighbourhood::Adjacent, surround_rule)
 }
 
 fn calc_occupied_visible(input: &str) -> usize {
-    let mut layout = SeatLayout::from(input);
-    let mut counter = 0;
-    while layout.apply_round_visible() {
-        counter += 1;
-    }
-
-    println!("Rounds count is {counter}");
-
-    layout.get_occupied_count()
+    let neighbourhood = Neighbourhood::LineOfSight(|state| *state == State::Floor);
+    calc_occupied(input, neighbourhood, visible_rule)
 }
 
 fn part_1(input: &str) {
@@ -1,72 +1,79 @@
 use std::collections::{HashSet, VecDeque};
 
-use crate::utls::read_text_from_file;
+use crate::{
+    automaton::{ADJACENT, DenseGrid, Pos, Storage},
+    utls::read_text_from_file,
+};
 
+/// Flashes spread within a step, so the octopuses share the grid and the neighbourhood with the
+/// automata but not the synchronous update.
 struct Octopus {
-    cells: [[u8; 10]; 10],
+    cells: DenseGrid<u8>,
     flash_count: usize,
 }
 
 impl From<&str> for Octopus {
     fn from(value: &str) -> Self {
-        let mut cells = [[0; 10]; 10];
-        for (i, row) in value.lines().enumerate() {
-            for (j, num) in row.chars().map(|ch| ch.to_digit(10).unwrap()).enumerate() {
-                cells[i][j] = num as u8;
-            }
-        }
+        let rows = value
+            .lines()
+            .map(|row| {
+                row.chars()
+                    .map(|ch| ch.to_digit(10).unwrap() as u8)
+                    .collect()
+            })
+            .collect();
         Octopus {
-            cells,
+            cells: DenseGrid::from_rows(rows, 0),
             flash_count: 0,
         }
     }
 }
 
 impl Octopus {
+    /// Increases the energy of the octopus, adding it to the flash queue if it flashes.
+    fn charge(&mut self, pos: Pos, flash_que: &mut VecDeque<Pos>, flash_set: &mut HashSet<Pos>) {
+        if flash_set.contains(&pos) {
+            return;
+        }
+
+        let energy = self.cells.get(pos) + 1;
+        if energy == 10 {
+            self.cells.set(pos, 0);
+            flash_que.push_back(pos);
+            flash_set.insert(pos);
+        } else {
+            self.cells.set(pos, energy);
+        }
+    }
+
     fn apply_step(&mut self) -> usize {
         let mut flash_que = VecDeque::new();
 
         let mut flash_set = HashSet::new();
 
         // Increase by one and fill the flash queue and set
-        for row in 0..10 {
-            for col in 0..10 {
-                self.cells[row][col] += 1;
-                if self.cells[row][col] == 10 {
-                    self.cells[row][col] = 0;
-                    flash_que.push_back((row, col));
-                    flash_set.insert((row, col));
-                }
-            }
+        for (pos, _) in self.cells.cells() {
+            self.charge(pos, &mut flash_que, &mut flash_set);
         }
 
         // Loop and flash
         while let Some((row, col)) = flash_que.pop_front() {
             self.flash_count += 1;
 
-            let row_start = row.saturating_sub(1);
-            let row_end = (row + 1).clamp(0, 9);
-            let col_start = col.saturating_sub(1);
-            let col_end = (col + 1).clamp(0, 9);
-
-            for r in row_start..=row_end {
-                for c in col_start..=col_end {
-                    if flash_set.contains(&(r, c)) {
-                        continue;
-                    }
-
-                    self.cells[r][c] += 1;
-                    if self.cells[r][c] == 10 {
-                        self.cells[r][c] = 0;
-                        flash_que.push_back((r, c));
-                        flash_set.insert((r, c));
-                    }
+            for (d_row, d_col) in ADJACENT {
+                let pos = (row + d_row, col + d_col);
+                if self.cells.contains(pos) {
+                    self.charge(pos, &mut flash_que, &mut flash_set);
                 }
             }
         }
 
         flash_set.len()
     }
+
+    fn size(&self) -> usize {
+        self.cells.cells().len()
+    }
 }
 
 fn calc_total_flashes(input: &str, steps: usize) -> usize {
@@ -82,7 +89,7 @@ fn calc_first_sync(input: &str) -> usize {
     let mut octopus = Octopus::from(input);
     let mut count = 1;
 
-    while octopus.apply_step() != 100 {
+    while octopus.apply_step() != octopus.size() {
         count += 1;
     }
 
@@ -1,6 +1,9 @@
 use std::collections::{HashMap, HashSet};
 
-use crate::utls::read_text_from_file;
+use crate::{
+    dot::{self, Graph, Shape},
+    utls::read_text_from_file,
+};
 
 #[derive(Debug, Hash, PartialEq, Eq, Clone)]
 enum Node {
@@ -26,6 +29,24 @@ impl From<&str> for Node {
     }
 }
 
+impl Node {
+    fn name(&self) -> &str {
+        match self {
+            Node::Start => "start",
+            Node::End => "end",
+            Node::Small(name) | Node::Big(name) => name,
+        }
+    }
+
+    fn shape(&self) -> Shape {
+        match self {
+            Node::Start | Node::End => Shape::DoubleCircle,
+            Node::Small(_) => Shape::Circle,
+            Node::Big(_) => Shape::Box,
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 struct WayState {
     way: Vec<Node>,
@@ -51,6 +72,19 @@ fn create_graph(input: &str) -> HashMap<Node, Vec<Node>> {
     graph
 }
 
+fn build_graph(caves: &HashMap<Node, Vec<Node>>) -> Graph {
+    let mut graph = Graph::undirected();
+    for (cave, neighbours) in caves {
+        graph.node(cave.name(), cave.shape());
+        // Each connection is saved from both sides.
+        for neighbour in neighbours.iter().filter(|n| cave.name() < n.name()) {
+            graph.edge(cave.name(), neighbour.name());
+        }
+    }
+
+    graph
+}
+
 fn find_distinct_paths(input: &str, allow_one_duplicate: bool) -> usize {
     let graph = create_graph(input);
 
@@ -118,6 +152,8 @@ fn part_1() {
     let answer = find_distinct_paths(&input, false);
 
     println!("Part 1 answer is {answer}");
+
+    dot::export("21_12_caves", || build_graph(&create_graph(&input)));
 }
 
 fn part_2() {
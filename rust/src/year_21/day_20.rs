@@ -1,6 +1,10 @@
 use std::fmt::Display;
 
-use crate::utls::read_text_from_file;
+use crate::{
+    automaton::{Automaton, DenseGrid, Neighbourhood, Storage},
+    image::{self, Image},
+    utls::read_text_from_file,
+};
 
 #[derive(Debug)]
 struct Picture {
@@ -44,39 +48,6 @@ impl Display for Picture {
     }
 }
 
-impl Picture {
-    fn apply_enhance(&mut self, enh_alg: &[bool], round: usize) {
-        let new_width = self.pixels[0].len() + 2;
-        let new_height = self.pixels.len() + 2;
-        let mut px = vec![vec![false; new_width]; new_height];
-
-        for (row_idx, row) in px.iter_mut().enumerate() {
-            for (col_idx, val) in row.iter_mut().enumerate() {
-                let mut grid = Vec::with_capacity(9);
-                for r in row_idx..row_idx + 3 {
-                    for c in col_idx..col_idx + 3 {
-                        grid.push(
-                            *self
-                                .pixels
-                                .get(r.wrapping_sub(2))
-                                .and_then(|row| row.get(c.wrapping_sub(2)))
-                                .unwrap_or(&(round & 1 == 1)),
-                        );
-                    }
-                }
-                let lid_idx = bool_slice_to_binary(&grid);
-                *val = enh_alg[lid_idx];
-            }
-        }
-
-        self.pixels = px;
-    }
-
-    fn get_lid_pixels(&self) -> usize {
-        self.pixels.iter().flatten().filter(|&&lid| lid).count()
-    }
-}
-
 fn bool_slice_to_binary(arr: &[bool]) -> usize {
     let mut result = 0;
     for &bit in arr {
@@ -112,14 +83,30 @@ fn parse_input(input: &str) -> (Vec<bool>, Picture) {
 }
 
 fn calc_lid_pixels(input: &str, target: usize) -> usize {
-    let (img_enh, mut picture) = parse_input(input);
+    let (img_enh, picture) = parse_input(input);
     println!("{picture}");
 
-    for i in 0..target {
-        picture.apply_enhance(&img_enh, i);
-    }
+    // The window of a pixel are its neighbours in reading order with the pixel in the middle.
+    let enhance = |lid: bool, neighbours: &[bool]| {
+        let (above, below) = neighbours.split_at(4);
+        let window: Vec<bool> = above.iter().chain([&lid]).chain(below).copied().collect();
+        img_enh[bool_slice_to_binary(&window)]
+    };
+
+    let grid = DenseGrid::from_rows(picture.pixels, false);
+    let mut automaton = Automaton::new(grid, Neighbourhood::Adjacent, enhance).infinite();
+    automaton.run(target);
+
+    image::export(&format!("21_20_round_{target}"), || {
+        Image::from_grid(&automaton.storage().to_rows(), |lit| image::binary(*lit))
+    });
+
+    assert!(
+        !automaton.storage().background(),
+        "infinite count of lid pixels"
+    );
 
-    picture.get_lid_pixels()
+    automaton.count(true)
 }
 
 fn part_1() {
@@ -1,4 +1,4 @@
-use crate::utls::read_text_from_file;
+use crate::{anim, utls::read_text_from_file};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum Cell {
@@ -18,6 +18,16 @@ impl From<char> for Cell {
     }
 }
 
+impl Cell {
+    fn get_char(&self) -> char {
+        match self {
+            Cell::East => '>',
+            Cell::South => 'v',
+            Cell::Empty => '.',
+        }
+    }
+}
+
 #[derive(Debug)]
 struct Grid {
     cells: Vec<Vec<Cell>>,
@@ -44,6 +54,7 @@ impl Grid {
             self.move_cells_east(&available_east);
             let available_south = self.get_cells_can_move_south();
             self.move_cells_south(&available_south);
+            anim::frame(format_args!("Round {rounds}"), || self.render());
 
             if available_east.is_empty() && available_south.is_empty() {
                 return rounds;
@@ -51,6 +62,17 @@ impl Grid {
         }
     }
 
+    fn render(&self) -> String {
+        self.cells
+            .iter()
+            .map(|row| {
+                let mut line: String = row.iter().map(Cell::get_char).collect();
+                line.push('\n');
+                line
+            })
+            .collect()
+    }
+
     fn get_cells_can_move_east(&self) -> Vec<(usize, usize)> {
         let rows = self.cells.len();
         let cols = self.cells[0].len();
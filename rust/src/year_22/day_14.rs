@@ -1,6 +1,6 @@
 use std::collections::HashSet;
 
-use crate::utls::read_text_from_file;
+use crate::{anim, utls::read_text_from_file};
 
 #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
 struct Point {
@@ -83,6 +83,44 @@ impl From<&str> for Path {
     }
 }
 
+const SAND_SOURCE: Point = Point { x: 500, y: 0 };
+
+/// Renders rocks as `#` and the rest sand as `o`, with the floor if given.
+fn render_cave(rocks: &HashSet<Point>, points: &HashSet<Point>, floor: Option<i32>) -> String {
+    let min_x = points.iter().map(|p| p.x).min().unwrap().min(SAND_SOURCE.x);
+    let max_x = points.iter().map(|p| p.x).max().unwrap().max(SAND_SOURCE.x);
+    let max_y = points.iter().map(|p| p.y).max().unwrap();
+
+    let mut frame = String::new();
+    for y in 0..=max_y {
+        for x in min_x..=max_x {
+            let point = Point::new(x, y);
+            let ch = if rocks.contains(&point) {
+                '#'
+            } else if points.contains(&point) {
+                'o'
+            } else if point == SAND_SOURCE {
+                '+'
+            } else {
+                '.'
+            };
+            frame.push(ch);
+        }
+        frame.push('\n');
+    }
+
+    if let Some(floor) = floor {
+        for _ in max_y + 1..floor {
+            frame.extend(std::iter::repeat_n('.', (max_x - min_x + 1) as usize));
+            frame.push('\n');
+        }
+        frame.extend(std::iter::repeat_n('#', (max_x - min_x + 1) as usize));
+        frame.push('\n');
+    }
+
+    frame
+}
+
 fn calc_rest_units(input: &str) -> usize {
     let paths: Vec<Path> = input.lines().map(Path::from).collect();
 
@@ -96,6 +134,7 @@ fn calc_rest_units(input: &str) -> usize {
         .into_iter()
         .flat_map(|path| path.get_all_points())
         .collect();
+    let rocks = points_hash.clone();
 
     let mut count_rest = 0;
     let mut sand_point = Point::new(500, 0);
@@ -122,6 +161,9 @@ fn calc_rest_units(input: &str) -> usize {
         points_hash.insert(sand_point);
         sand_point = Point::new(500, 0);
         count_rest += 1;
+        anim::frame(format_args!("Sand units: {count_rest}"), || {
+            render_cave(&rocks, &points_hash, None)
+        });
     }
 
     count_rest
@@ -150,6 +192,7 @@ fn calc_rest_sand_with_floor(input: &str) -> usize {
         .into_iter()
         .flat_map(|path| path.get_all_points())
         .collect();
+    let rocks = points_hash.clone();
 
     let mut count_rest = 0;
     let mut sand_point = Point::new(500, 0);
@@ -177,6 +220,9 @@ fn calc_rest_sand_with_floor(input: &str) -> usize {
         points_hash.insert(sand_point);
         sand_point = Point::new(500, 0);
         count_rest += 1;
+        anim::frame(format_args!("Sand units: {count_rest}"), || {
+            render_cave(&rocks, &points_hash, Some(floor))
+        });
     }
 
     count_rest
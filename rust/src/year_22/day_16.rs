@@ -2,7 +2,10 @@ use std::collections::BTreeMap;
 
 use itertools::Itertools;
 
-use crate::utls::read_text_from_file;
+use crate::{
+    dot::{self, Graph, Shape},
+    utls::read_text_from_file,
+};
 
 #[derive(Debug, Hash, Clone, Ord, PartialOrd, PartialEq, Eq)]
 struct Valve {
@@ -36,6 +39,30 @@ impl From<&str> for Valve {
     }
 }
 
+const START_VALVE: &str = "AA";
+
+/// Builds the graph of the tunnels with the flow rates in the labels. Valves with flow are
+/// drawn with double circles and the start as box.
+fn build_graph(valves: &[Valve]) -> Graph {
+    let mut graph = Graph::undirected();
+    for valve in valves {
+        let shape = match valve {
+            _ if valve.name == START_VALVE => Shape::Box,
+            _ if valve.rate > 0 => Shape::DoubleCircle,
+            _ => Shape::Circle,
+        };
+        let label = format!("{}\nrate={}", valve.name, valve.rate);
+        graph.labelled_node(&valve.name, label, shape);
+
+        // Tunnels are listed from both sides.
+        for target in valve.lead_to.iter().filter(|target| valve.name < **target) {
+            graph.edge(&valve.name, target);
+        }
+    }
+
+    graph
+}
+
 #[derive(Debug, PartialOrd, Ord, PartialEq, Eq, Clone)]
 struct Pair<'a>(&'a str, &'a str);
 
@@ -181,6 +208,11 @@ fn part_1() {
     let answer = calc_most_pressure(&input);
 
     println!("Part 1 answer is {answer}");
+
+    dot::export("22_16_valves", || {
+        let valves: Vec<Valve> = input.lines().map(Valve::from).collect();
+        build_graph(&valves)
+    });
 }
 
 fn part_2() {
@@ -1,6 +1,6 @@
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 
-use crate::utls::read_text_from_file;
+use crate::{anim, utls::read_text_from_file};
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
 enum Dir {
@@ -126,6 +126,9 @@ impl Tetris {
     }
 }
 
+/// Count of rows from the top of the tower in animation frames.
+const FRAME_ROWS: usize = 40;
+
 struct Game {
     tetris_gen: Box<dyn Iterator<Item = Shape>>,
     dirs_gen: Box<dyn Iterator<Item = Dir>>,
@@ -166,8 +169,37 @@ impl Game {
         }
     }
 
+    /// Renders the top of the tower with the falling rock as `@`.
+    fn render(&self, falling: &[Point]) -> String {
+        let top = falling.iter().map(|p| p.y).max().unwrap_or_default();
+        let bottom = top.saturating_sub(FRAME_ROWS);
+        let rocks: BTreeSet<_> = self.rocks.iter().filter(|p| p.y >= bottom).collect();
+
+        let mut frame = String::new();
+        for y in (bottom.max(1)..=top).rev() {
+            frame.push('|');
+            for x in 0..7 {
+                let point = Point::new(x, y);
+                let ch = if falling.contains(&point) {
+                    '@'
+                } else if rocks.contains(&point) {
+                    '#'
+                } else {
+                    '.'
+                };
+                frame.push(ch);
+            }
+            frame.push_str("|\n");
+        }
+        if bottom == 0 {
+            frame.push_str("+-------+\n");
+        }
+
+        frame
+    }
+
     fn simulate(&mut self, target: usize) -> usize {
-        for _ in 0..target {
+        for idx in 0..target {
             let shape = self.tetris_gen.next().unwrap();
             let max_y = self.rocks.iter().map(|p| p.y).max().unwrap();
             let mut tetris = Tetris::default();
@@ -184,6 +216,9 @@ impl Game {
                 }
 
                 count += 1;
+                anim::frame(format_args!("Rock {}", idx + 1), || {
+                    self.render(&tetris.points)
+                });
             }
 
             tetris.points.into_iter().for_each(|p| {
@@ -1,10 +1,14 @@
 use std::{
     collections::{BTreeSet, BinaryHeap},
     num::ParseIntError,
+    ops::RangeInclusive,
     str::FromStr,
 };
 
-use crate::utls::read_text_from_file;
+use crate::{
+    image::{self, Image, Rgb},
+    utls::read_text_from_file,
+};
 
 #[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Ord, Eq)]
 struct Cube {
@@ -72,6 +76,49 @@ fn calc_surface_area(input: &str) -> usize {
         .count()
 }
 
+const LAVA_COLOR: Rgb = [255, 96, 0];
+const POCKET_COLOR: Rgb = [255, 230, 64];
+const EXTERIOR_COLOR: Rgb = [24, 32, 72];
+const SLICE_SCALE: usize = 8;
+
+/// Draws the slices of the droplet along the z axis next to each other in a square layout.
+/// Lava, trapped air pockets and the exterior have different colors.
+fn slices_image(
+    cubes: &BTreeSet<Cube>,
+    exterior: &BTreeSet<Cube>,
+    x_range: &RangeInclusive<isize>,
+    y_range: &RangeInclusive<isize>,
+    z_range: &RangeInclusive<isize>,
+) -> Image {
+    let width = x_range.clone().count();
+    let height = y_range.clone().count();
+    let slices = z_range.clone().count();
+    let columns = (1..).find(|cols| cols * cols >= slices).unwrap();
+    let rows = slices.div_ceil(columns);
+
+    // Keep a gap of one pixel between the slices.
+    let mut image = Image::new(columns * (width + 1) - 1, rows * (height + 1) - 1);
+    for (idx, z) in z_range.clone().enumerate() {
+        let left = (idx % columns) * (width + 1);
+        let top = (idx / columns) * (height + 1);
+        for (row, y) in y_range.clone().enumerate() {
+            for (col, x) in x_range.clone().enumerate() {
+                let cube = Cube::new(x, y, z);
+                let color = if cubes.contains(&cube) {
+                    LAVA_COLOR
+                } else if exterior.contains(&cube) {
+                    EXTERIOR_COLOR
+                } else {
+                    POCKET_COLOR
+                };
+                image.set(left + col, top + row, color);
+            }
+        }
+    }
+
+    image.scale(SLICE_SCALE)
+}
+
 fn calc_exterior_surface(input: &str) -> usize {
     let (mut min_x, mut max_x, mut min_y, mut max_y, mut min_z, mut max_z) = (
         isize::MAX,
@@ -116,6 +163,10 @@ fn calc_exterior_surface(input: &str) -> usize {
         }
     }
 
+    image::export("22_18_slices", || {
+        slices_image(&cubes, &exterior_neighbors, &x_range, &y_range, &z_range)
+    });
+
     cubes
         .iter()
         .copied()
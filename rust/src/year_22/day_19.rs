@@ -1,6 +1,12 @@
-use std::{collections::VecDeque, num::ParseIntError, str::FromStr, sync::mpsc, thread};
+use std::{collections::HashMap, env, num::ParseIntError, str::FromStr};
 
-use crate::utls::read_text_from_file;
+use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+
+use crate::utls::{find_arg_value, read_text_from_file};
+
+/// The most non dominated states kept for the same minutes and robots. Dropping the oldest one
+/// only loses pruning, so this bounds the memory without changing the result.
+const MAX_FRONTIER: usize = 8;
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
 enum Robot {
@@ -37,6 +43,19 @@ impl FromStr for RobotCost {
     }
 }
 
+impl Robot {
+    const ALL: [Robot; 4] = [Robot::Ore, Robot::Clay, Robot::Obsidian, Robot::Geode];
+
+    fn name(self) -> &'static str {
+        match self {
+            Robot::Ore => "ore",
+            Robot::Clay => "clay",
+            Robot::Obsidian => "obsidian",
+            Robot::Geode => "geode",
+        }
+    }
+}
+
 impl RobotCost {
     fn new(kind: Robot, cost_ore: usize, cost_clay: usize, cost_obsidian: usize) -> Self {
         Self {
@@ -47,30 +66,8 @@ impl RobotCost {
         }
     }
 
-    fn can_build(&self, state: &State, max_val: &RobotCost) -> bool {
-        let built_robots = state.robots.iter().filter(|&&r| r == self.robot).count();
-        let maxed_out = match self.robot {
-            Robot::Ore => built_robots >= max_val.cost_ore,
-            Robot::Clay => built_robots >= max_val.cost_clay,
-            Robot::Obsidian => built_robots >= max_val.cost_obsidian,
-            Robot::Geode => false,
-        };
-
-        !maxed_out
-            && state.ore_count >= self.cost_ore
-            && state.clay_count >= self.cost_clay
-            && state.obsidian_count >= self.cost_obsidian
-    }
-
-    fn build(&self, state: &mut State) {
-        // make sure program will crash if overflow happened
-        state.ore_count = state.ore_count.checked_sub(self.cost_ore).unwrap();
-        state.clay_count = state.clay_count.checked_sub(self.cost_clay).unwrap();
-        state.obsidian_count = state
-            .obsidian_count
-            .checked_sub(self.cost_obsidian)
-            .unwrap();
-        state.robots.push(self.robot);
+    fn costs(&self) -> [usize; 3] {
+        [self.cost_ore, self.cost_clay, self.cost_obsidian]
     }
 }
 
@@ -100,144 +97,231 @@ impl FromStr for Blueprint {
     }
 }
 
-#[derive(Default, Debug, Clone)]
+/// Geodes are counted for all the remaining minutes once their robot is built, so only the
+/// robots and the resources for the other kinds are tracked.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 struct State {
     minutes: usize,
-    ore_count: usize,
-    clay_count: usize,
-    obsidian_count: usize,
-    geode_count: usize,
-    robots: Vec<Robot>,
+    /// Ore, clay and obsidian robots.
+    robots: [usize; 3],
+    /// Ore, clay and obsidian.
+    resources: [usize; 3],
+    geodes: usize,
 }
 
 impl State {
-    fn collect(&mut self) {
-        self.robots.iter().for_each(|r| match r {
-            Robot::Ore => self.ore_count += 1,
-            Robot::Clay => self.clay_count += 1,
-            Robot::Obsidian => self.obsidian_count += 1,
-            Robot::Geode => self.geode_count += 1,
-        });
+    fn new(minutes: usize) -> Self {
+        Self {
+            minutes,
+            robots: [1, 0, 0],
+            resources: [0; 3],
+            geodes: 0,
+        }
     }
 
-    fn estimate_best_result(&self, robot: Robot) -> usize {
-        let element_count = match robot {
-            Robot::Ore => self.ore_count,
-            Robot::Clay => self.clay_count,
-            Robot::Obsidian => self.obsidian_count,
-            Robot::Geode => self.geode_count,
-        };
-
-        element_count
-            + self.robots.iter().filter(|&&r| r == robot).count() * self.minutes
-            + self.minutes * (self.minutes.checked_sub(1).unwrap()) / 2
-    }
-}
+    /// Waits until the robot can be afforded and builds it, skipping the idle minutes.
+    /// Returns the minutes it took with the new state, or None if the robot can't be built
+    /// in time to be of any use.
+    fn build(&self, robot: Robot, costs: [usize; 3]) -> Option<(usize, State)> {
+        let mut wait = 0;
+        for ((cost, resource), robots) in costs.iter().zip(self.resources).zip(self.robots) {
+            if *cost > resource {
+                if robots == 0 {
+                    return None;
+                }
+                wait = wait.max((cost - resource).div_ceil(robots));
+            }
+        }
 
-impl Blueprint {
-    fn get_max_costs(&self) -> RobotCost {
-        let mut max_ore = 0;
-        let mut max_clay = 0;
-        let mut max_obsidian = 0;
-
-        self.robot_costs.iter().for_each(|c| {
-            max_ore = max_ore.max(c.cost_ore);
-            max_clay = max_clay.max(c.cost_clay);
-            max_obsidian = max_obsidian.max(c.cost_obsidian);
+        // The robot is ready in the minute after it's built.
+        let elapsed = wait + 1;
+        if elapsed >= self.minutes {
+            return None;
+        }
+
+        let mut next = *self;
+        next.minutes -= elapsed;
+        next.resources = std::array::from_fn(|idx| {
+            self.resources[idx] + self.robots[idx] * elapsed - costs[idx]
         });
+        match robot {
+            Robot::Geode => next.geodes += next.minutes,
+            kind => next.robots[kind as usize] += 1,
+        }
 
-        RobotCost::new(Robot::Geode, max_ore, max_clay, max_obsidian)
+        Some((elapsed, next))
     }
 
-    fn get_max_geodes(&self, target: usize) -> usize {
-        let state = State {
-            robots: vec![Robot::Ore],
-            minutes: target,
-            ..Default::default()
-        };
+    /// The geodes if ore was free and an obsidian robot could be built each minute alongside
+    /// a geode robot whenever the obsidian is enough.
+    fn upper_bound(&self, geode_obsidian: usize) -> usize {
+        let mut obsidian = self.resources[2];
+        let mut geodes = self.geodes;
+        for (obsidian_robots, remaining) in (self.robots[2]..).zip((0..self.minutes).rev()) {
+            if obsidian >= geode_obsidian {
+                obsidian -= geode_obsidian;
+                geodes += remaining;
+            }
+            obsidian += obsidian_robots;
+        }
 
-        let max_val = self.get_max_costs();
+        geodes
+    }
+}
 
-        let mut queue = VecDeque::from([state]);
+/// A robot built in the given minute, counting from one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Build {
+    minute: usize,
+    robot: Robot,
+}
 
-        let mut max_geode = 0;
+/// Depth first search over the next robot to build, keeping the build order of the best result.
+struct Search {
+    minutes: usize,
+    costs: [[usize; 3]; 4],
+    /// No robot can spend more of a resource per minute than the most expensive one.
+    max_costs: [usize; 3],
+    best: usize,
+    builds: Vec<Build>,
+    best_builds: Vec<Build>,
+    /// The resources and geodes of the states seen for the minutes and the robots, where only
+    /// the states not dominated by another one are kept, up to [`MAX_FRONTIER`] of them.
+    seen: HashMap<(usize, [usize; 3]), Vec<[usize; 4]>>,
+}
 
-        while let Some(mut state) = queue.pop_back() {
-            if state.minutes == 1 {
-                state.collect();
-                max_geode = max_geode.max(state.geode_count);
-                continue;
-            }
+impl Search {
+    fn new(blueprint: &Blueprint, minutes: usize) -> Self {
+        let mut costs = [[0; 3]; 4];
+        for robot_cost in &blueprint.robot_costs {
+            costs[robot_cost.robot as usize] = robot_cost.costs();
+        }
+        let max_costs =
+            std::array::from_fn(|idx| costs.iter().map(|cost| cost[idx]).max().unwrap());
 
-            if state.estimate_best_result(Robot::Geode) < max_geode {
-                continue;
-            }
+        Self {
+            minutes,
+            costs,
+            max_costs,
+            best: 0,
+            builds: Vec::new(),
+            best_builds: Vec::new(),
+            seen: HashMap::new(),
+        }
+    }
+
+    /// Checks if a seen state has at least the resources and geodes of this one with the same
+    /// robots and minutes, remembering the state otherwise.
+    fn is_dominated(&mut self, state: &State) -> bool {
+        // Resources beyond what can be spent in the remaining minutes make no difference.
+        let values: [usize; 4] = std::array::from_fn(|idx| match idx {
+            3 => state.geodes,
+            idx => state.resources[idx].min(self.max_costs[idx] * state.minutes),
+        });
+
+        let frontier = self.seen.entry((state.minutes, state.robots)).or_default();
+        let dominates = |a: &[usize; 4], b: &[usize; 4]| a.iter().zip(b).all(|(a, b)| a >= b);
+        if frontier.iter().any(|seen| dominates(seen, &values)) {
+            return true;
+        }
 
-            state.minutes -= 1;
+        frontier.retain(|seen| !dominates(&values, seen));
+        if frontier.len() == MAX_FRONTIER {
+            frontier.remove(0);
+        }
+        frontier.push(values);
+
+        false
+    }
 
-            if self.robot_costs.last().unwrap().can_build(&state, &max_val) {
-                state.collect();
+    fn search(&mut self, state: State) {
+        if state.geodes > self.best {
+            self.best = state.geodes;
+            self.best_builds = self.builds.clone();
+        }
 
-                self.robot_costs.last().unwrap().build(&mut state);
+        let geode_obsidian = self.costs[Robot::Geode as usize][2];
+        if state.upper_bound(geode_obsidian) <= self.best || self.is_dominated(&state) {
+            return;
+        }
 
-                queue.push_back(state);
+        // Geode robots first to find good results early for the bound.
+        for robot in Robot::ALL.into_iter().rev() {
+            if robot != Robot::Geode
+                && state.robots[robot as usize] >= self.max_costs[robot as usize]
+            {
                 continue;
             }
 
-            // The don't build option
-            let mut clone = state.clone();
-            clone.collect();
-            queue.push_back(clone);
-
-            // Building robots possibilities
-            self.robot_costs
-                .iter()
-                .filter(|r| r.can_build(&state, &max_val))
-                .for_each(|r| {
-                    let mut clone = state.clone();
-                    clone.collect();
-                    r.build(&mut clone);
-
-                    queue.push_back(clone);
-                });
+            if let Some((elapsed, next)) = state.build(robot, self.costs[robot as usize]) {
+                let minute = self.minutes - state.minutes + elapsed;
+                self.builds.push(Build { minute, robot });
+                self.search(next);
+                self.builds.pop();
+            }
         }
+    }
+}
 
-        max_geode
+impl Blueprint {
+    /// Returns the max count of geodes with the build order opening them.
+    fn find_best_builds(&self, minutes: usize) -> (usize, Vec<Build>) {
+        let mut search = Search::new(self, minutes);
+        search.search(State::new(minutes));
+
+        (search.best, search.best_builds)
+    }
+
+    fn get_max_geodes(&self, target: usize) -> usize {
+        self.find_best_builds(target).0
     }
 
     fn get_score(&self, target: usize) -> usize {
         self.id as usize * self.get_max_geodes(target)
     }
-}
 
-fn calc_part_1(input: &str) -> usize {
-    let (tx, rx) = mpsc::channel();
-    for blue_print in input.lines().flat_map(Blueprint::from_str) {
-        let tx_clone = tx.clone();
-        thread::spawn(move || {
-            let score = blue_print.get_score(24);
-            tx_clone.send(score).unwrap();
-        });
+    fn explain(&self, minutes: usize) {
+        let (geodes, builds) = self.find_best_builds(minutes);
+        println!(
+            "Blueprint {} opens {geodes} geodes in {minutes} minutes:",
+            self.id
+        );
+        for build in builds {
+            println!("  minute {:2}: {} robot", build.minute, build.robot.name());
+        }
     }
+}
 
-    drop(tx);
+fn parse_blueprints(input: &str) -> Vec<Blueprint> {
+    input.lines().flat_map(Blueprint::from_str).collect()
+}
 
-    rx.into_iter().sum()
+fn calc_part_1(input: &str) -> usize {
+    parse_blueprints(input)
+        .par_iter()
+        .map(|blueprint| blueprint.get_score(24))
+        .sum()
 }
 
 fn calc_part_2(input: &str) -> usize {
-    let (tx, rx) = mpsc::channel();
-
-    for blue_print in input.lines().take(3).flat_map(Blueprint::from_str) {
-        let tx_clone = tx.clone();
-        thread::spawn(move || {
-            let score = blue_print.get_max_geodes(32);
-            tx_clone.send(score).unwrap();
-        });
-    }
-    drop(tx);
+    parse_blueprints(input)
+        .iter()
+        .take(3)
+        .map(|blueprint| blueprint.get_max_geodes(32))
+        .product()
+}
 
-    rx.into_iter().product()
+/// Prints the build orders of the blueprint for both parts.
+fn explain(input: &str, id: &str) {
+    let id: u8 = id.parse().expect("--explain needs a blueprint id");
+    let blueprints = parse_blueprints(input);
+    let blueprint = blueprints
+        .iter()
+        .find(|blueprint| blueprint.id == id)
+        .unwrap_or_else(|| panic!("no blueprint with id {id}"));
+
+    blueprint.explain(24);
+    blueprint.explain(32);
 }
 
 fn part_1() {
@@ -257,6 +341,12 @@ fn part_2() {
 }
 
 pub fn run() {
+    // Print the build orders of a blueprint with `--explain <id>` instead of solving.
+    if let Some(id) = find_arg_value(env::args().skip(1), "--explain") {
+        explain(&read_text_from_file("22", "19"), &id);
+        return;
+    }
+
     part_1();
     part_2();
 }
@@ -272,4 +362,59 @@ Blueprint 2: Each ore robot costs 2 ore. Each clay robot costs 3 ore. Each obsid
     fn test_part_1() {
         assert_eq!(calc_part_1(INPUT), 33);
     }
+
+    #[test]
+    fn test_part_2() {
+        let blueprints = parse_blueprints(INPUT);
+        assert_eq!(blueprints[0].get_max_geodes(32), 56);
+        assert_eq!(blueprints[1].get_max_geodes(32), 62);
+    }
+
+    /// Replays the build order minute by minute, returning the opened geodes.
+    fn replay(blueprint: &Blueprint, builds: &[Build], minutes: usize) -> usize {
+        let mut robots = [1, 0, 0, 0];
+        let mut resources = [0_usize; 4];
+        for minute in 1..=minutes {
+            let build = builds.iter().find(|build| build.minute == minute);
+            if let Some(build) = build {
+                let cost = blueprint
+                    .robot_costs
+                    .iter()
+                    .find(|cost| cost.robot == build.robot)
+                    .unwrap()
+                    .costs();
+                for (resource, cost) in resources.iter_mut().zip(cost) {
+                    *resource = resource.checked_sub(cost).unwrap();
+                }
+            }
+            for (resource, robots) in resources.iter_mut().zip(robots) {
+                *resource += robots;
+            }
+            if let Some(build) = build {
+                robots[build.robot as usize] += 1;
+            }
+        }
+
+        resources[3]
+    }
+
+    #[test]
+    fn test_best_builds() {
+        for blueprint in parse_blueprints(INPUT) {
+            for minutes in [24, 32] {
+                let (geodes, builds) = blueprint.find_best_builds(minutes);
+                assert_eq!(replay(&blueprint, &builds, minutes), geodes);
+            }
+        }
+
+        let blueprint = &parse_blueprints(INPUT)[0];
+        let (_, builds) = blueprint.find_best_builds(24);
+        assert_eq!(
+            builds.first(),
+            Some(&Build {
+                minute: 3,
+                robot: Robot::Clay
+            })
+        );
+    }
 }
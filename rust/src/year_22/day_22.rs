@@ -1,4 +1,4 @@
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashMap};
 
 use crate::utls::read_text_from_file;
 
@@ -55,7 +55,7 @@ impl From<&str> for Note {
             })
             .collect();
 
-        let inst_chars: Vec<char> = inst.chars().collect();
+        let inst_chars: Vec<char> = inst.trim().chars().collect();
 
         let mut instructions = Vec::new();
 
@@ -93,7 +93,7 @@ impl From<&str> for Note {
     }
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum Facing {
     Right = 0,
     Down = 1,
@@ -101,6 +101,199 @@ enum Facing {
     Up = 3,
 }
 
+impl Facing {
+    const ALL: [Facing; 4] = [Facing::Right, Facing::Down, Facing::Left, Facing::Up];
+
+    fn turn_left(self) -> Facing {
+        match self {
+            Facing::Right => Facing::Up,
+            Facing::Down => Facing::Right,
+            Facing::Left => Facing::Down,
+            Facing::Up => Facing::Left,
+        }
+    }
+
+    fn turn_right(self) -> Facing {
+        match self {
+            Facing::Right => Facing::Down,
+            Facing::Down => Facing::Left,
+            Facing::Left => Facing::Up,
+            Facing::Up => Facing::Right,
+        }
+    }
+
+    fn step(self, point: Point) -> Option<Point> {
+        match self {
+            Facing::Right => Some(Point::new(point.row, point.col + 1)),
+            Facing::Down => Some(Point::new(point.row + 1, point.col)),
+            Facing::Left => point
+                .col
+                .checked_sub(1)
+                .map(|col| Point::new(point.row, col)),
+            Facing::Up => point
+                .row
+                .checked_sub(1)
+                .map(|row| Point::new(row, point.col)),
+        }
+    }
+}
+
+type Vec3 = [i32; 3];
+
+fn neg(v: Vec3) -> Vec3 {
+    [-v[0], -v[1], -v[2]]
+}
+
+fn dot(a: Vec3, b: Vec3) -> i32 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+/// A face of the cube net with its orientation after folding: the outward normal and the
+/// directions of the net columns (right) and rows (down) on the cube.
+#[derive(Debug, Clone, Copy)]
+struct Face {
+    top_left: Point,
+    normal: Vec3,
+    right: Vec3,
+    down: Vec3,
+}
+
+impl Face {
+    fn dir_vec(&self, facing: Facing) -> Vec3 {
+        match facing {
+            Facing::Right => self.right,
+            Facing::Down => self.down,
+            Facing::Left => neg(self.right),
+            Facing::Up => neg(self.down),
+        }
+    }
+
+    /// Orientation of the neighbour face after folding it over the edge in the given direction.
+    fn fold(&self, facing: Facing, top_left: Point) -> Face {
+        let (normal, right, down) = match facing {
+            Facing::Right => (self.right, neg(self.normal), self.down),
+            Facing::Down => (self.down, self.right, neg(self.normal)),
+            Facing::Left => (neg(self.right), self.normal, self.down),
+            Facing::Up => (neg(self.down), self.right, self.normal),
+        };
+
+        Face {
+            top_left,
+            normal,
+            right,
+            down,
+        }
+    }
+}
+
+/// The cube folded out of the map, which works with any of the 11 cube nets.
+#[derive(Debug)]
+struct Cube {
+    size: usize,
+    faces: Vec<Face>,
+    // Maps the position of the face in the net grid to its index.
+    net: HashMap<(usize, usize), usize>,
+}
+
+impl Cube {
+    fn new(map: &BTreeMap<Point, Tile>) -> Self {
+        let size = (map.len() / 6).isqrt();
+        assert_eq!(size * size * 6, map.len(), "map can't be folded to a cube");
+
+        let blocks: Vec<(usize, usize)> = map
+            .keys()
+            .filter(|p| (p.row - 1) % size == 0 && (p.col - 1) % size == 0)
+            .map(|p| ((p.row - 1) / size, (p.col - 1) / size))
+            .collect();
+        assert_eq!(blocks.len(), 6, "map must have six faces");
+
+        let first = blocks[0];
+        let mut faces = vec![Face {
+            top_left: Point::new(first.0 * size + 1, first.1 * size + 1),
+            normal: [0, 0, -1],
+            right: [1, 0, 0],
+            down: [0, 1, 0],
+        }];
+        let mut net = HashMap::from([(first, 0)]);
+
+        // Fold the faces one by one starting from the first one.
+        let mut queue = vec![first];
+        while let Some(block) = queue.pop() {
+            let face = faces[net[&block]];
+            for facing in Facing::ALL {
+                let Some(next) = facing
+                    .step(Point::new(block.0, block.1))
+                    .map(|p| (p.row, p.col))
+                    .filter(|next| blocks.contains(next) && !net.contains_key(next))
+                else {
+                    continue;
+                };
+
+                let top_left = Point::new(next.0 * size + 1, next.1 * size + 1);
+                net.insert(next, faces.len());
+                faces.push(face.fold(facing, top_left));
+                queue.push(next);
+            }
+        }
+
+        assert_eq!(faces.len(), 6, "faces of the net must be connected");
+        for (idx, face) in faces.iter().enumerate() {
+            assert!(
+                faces[..idx].iter().all(|other| other.normal != face.normal),
+                "net doesn't fold into a cube"
+            );
+        }
+
+        Self { size, faces, net }
+    }
+
+    fn face_of(&self, point: Point) -> &Face {
+        let block = ((point.row - 1) / self.size, (point.col - 1) / self.size);
+        &self.faces[self.net[&block]]
+    }
+
+    /// Moves over the edge of the face to the neighbour face on the cube.
+    ///
+    /// Using doubled coordinates with the cube center at origin, each tile has a position in
+    /// space. The tile behind the edge is one step further in the moving direction and one step
+    /// deeper in the old normal, and the new facing points along the old inward normal.
+    fn wrap(&self, point: Point, facing: Facing) -> (Point, Facing) {
+        let size = self.size as i32;
+        let face = self.face_of(point);
+        let row = (point.row - face.top_left.row) as i32;
+        let col = (point.col - face.top_left.col) as i32;
+
+        let pos: Vec3 = std::array::from_fn(|i| {
+            size * face.normal[i]
+                + (2 * col + 1 - size) * face.right[i]
+                + (2 * row + 1 - size) * face.down[i]
+        });
+
+        let dir = face.dir_vec(facing);
+        let next_pos: Vec3 = std::array::from_fn(|i| pos[i] + dir[i] - face.normal[i]);
+
+        let next_face = self
+            .faces
+            .iter()
+            .find(|f| f.normal == dir)
+            .expect("each direction has a face");
+        let next_facing = Facing::ALL
+            .into_iter()
+            .find(|&f| next_face.dir_vec(f) == neg(face.normal))
+            .unwrap();
+
+        let next_col = (dot(next_pos, next_face.right) + size - 1) / 2;
+        let next_row = (dot(next_pos, next_face.down) + size - 1) / 2;
+
+        let next_point = Point::new(
+            next_face.top_left.row + next_row as usize,
+            next_face.top_left.col + next_col as usize,
+        );
+
+        (next_point, next_facing)
+    }
+}
+
 #[derive(Debug)]
 struct State {
     pos: Point,
@@ -116,22 +309,8 @@ impl State {
 impl Inst {
     fn apply(&self, state: &mut State, map: &BTreeMap<Point, Tile>) {
         match self {
-            Inst::RotateLeft => {
-                state.facing = match state.facing {
-                    Facing::Right => Facing::Up,
-                    Facing::Down => Facing::Right,
-                    Facing::Left => Facing::Down,
-                    Facing::Up => Facing::Left,
-                }
-            }
-            Inst::RotateRight => {
-                state.facing = match state.facing {
-                    Facing::Right => Facing::Down,
-                    Facing::Down => Facing::Left,
-                    Facing::Left => Facing::Up,
-                    Facing::Up => Facing::Right,
-                }
-            }
+            Inst::RotateLeft => state.facing = state.facing.turn_left(),
+            Inst::RotateRight => state.facing = state.facing.turn_right(),
             Inst::Move(num) => {
                 for _ in 0..*num {
                     match state.facing {
@@ -229,274 +408,16 @@ impl Inst {
         }
     }
 
-    //      1111122222
-    //      1111122222
-    //      1111122222
-    //      1111122222
-    //      33333
-    //      33333
-    //      33333
-    //      33333
-    //      33333
-    // 5555544444
-    // 5555544444
-    // 5555544444
-    // 5555544444
-    // 5555544444
-    // 66666
-    // 66666
-    // 66666
-    // 66666
-    // 66666
-
-    fn apply_cube(&self, state: &mut State, map: &BTreeMap<Point, Tile>) {
+    fn apply_cube(&self, state: &mut State, map: &BTreeMap<Point, Tile>, cube: &Cube) {
         match self {
-            Inst::RotateLeft => {
-                state.facing = match state.facing {
-                    Facing::Right => Facing::Up,
-                    Facing::Down => Facing::Right,
-                    Facing::Left => Facing::Down,
-                    Facing::Up => Facing::Left,
-                }
-            }
-            Inst::RotateRight => {
-                state.facing = match state.facing {
-                    Facing::Right => Facing::Down,
-                    Facing::Down => Facing::Left,
-                    Facing::Left => Facing::Up,
-                    Facing::Up => Facing::Right,
-                }
-            }
+            Inst::RotateLeft => state.facing = state.facing.turn_left(),
+            Inst::RotateRight => state.facing = state.facing.turn_right(),
             Inst::Move(num) => {
                 for _ in 0..*num {
-                    let mut point = state.pos;
-                    let mut facing = state.facing;
-                    match state.facing {
-                        Facing::Right => {
-                            // Easy path
-                            if point.col % 50 != 0 {
-                                point.col += 1;
-                            } else {
-                                // Cube calculations
-                                match point.row {
-                                    // 1 & 2
-                                    (1..=50) => {
-                                        match point.col {
-                                            // 1 -> 2
-                                            100 => {
-                                                point.col += 1;
-                                            }
-                                            // 2 -> 4
-                                            150 => {
-                                                point.row = 151 - point.row;
-                                                point.col = 100;
-                                                facing = Facing::Left;
-                                            }
-                                            _ => unreachable!(),
-                                        }
-                                    }
-                                    // 3 -> 2
-                                    (51..=100) => {
-                                        assert!((51..=100).contains(&point.col));
-                                        point.col = point.row + 50;
-                                        point.row = 50;
-                                        facing = Facing::Up;
-                                    }
-                                    // 4 & 5
-                                    (101..=150) => {
-                                        match point.col {
-                                            // 5 -> 4
-                                            50 => {
-                                                point.col += 1;
-                                            }
-                                            // 4 -> 2
-                                            100 => {
-                                                point.row = 151 - point.row;
-                                                point.col = 150;
-                                                facing = Facing::Left;
-                                            }
-                                            _ => unreachable!(),
-                                        }
-                                    }
-                                    // 6 -> 4
-                                    (151..=200) => {
-                                        assert!((1..=51).contains(&point.col));
-                                        point.col = point.row - 100;
-                                        point.row = 150;
-                                        facing = Facing::Up;
-                                    }
-                                    _ => unreachable!(),
-                                }
-                            }
-                        }
-                        Facing::Left => {
-                            // Easy path
-                            if (point.col - 1) % 50 != 0 {
-                                point.col -= 1;
-                            } else {
-                                // Cube calculations
-                                match point.row {
-                                    // 1 & 2
-                                    (1..=50) => {
-                                        match point.col {
-                                            // 1 -> 5
-                                            51 => {
-                                                point.row = 151 - point.row;
-                                                point.col = 1;
-                                                facing = Facing::Right;
-                                            }
-                                            // 2 -> 1
-                                            101 => {
-                                                point.col -= 1;
-                                            }
-                                            _ => unreachable!(),
-                                        }
-                                    }
-                                    // 3 -> 5
-                                    (51..=100) => {
-                                        assert!((51..=100).contains(&point.col));
-                                        point.col = point.row - 50;
-                                        point.row = 101;
-                                        facing = Facing::Down;
-                                    }
-                                    // 4 & 5
-                                    (101..=150) => {
-                                        match point.col {
-                                            // 5 -> 1
-                                            1 => {
-                                                point.row = 151 - point.row;
-                                                point.col = 51;
-                                                facing = Facing::Right;
-                                            }
-                                            // 4 -> 5
-                                            51 => {
-                                                point.col -= 1;
-                                            }
-                                            _ => unreachable!(),
-                                        }
-                                    }
-                                    // 6 -> 1
-                                    (151..=200) => {
-                                        assert!((1..=51).contains(&point.col));
-                                        point.col = point.row - 100;
-                                        point.row = 1;
-                                        facing = Facing::Down;
-                                    }
-                                    _ => unreachable!(),
-                                }
-                            }
-                        }
-                        Facing::Down => {
-                            // Easy path
-                            if point.row % 50 != 0 {
-                                point.row += 1;
-                            } else {
-                                // Cube calculations
-                                match point.row {
-                                    // 1 & 2
-                                    50 => {
-                                        match point.col {
-                                            // 1 -> 3
-                                            (51..=100) => {
-                                                point.row += 1;
-                                            }
-                                            // 2 -> 3
-                                            (101..=150) => {
-                                                point.row = point.col - 50;
-                                                point.col = 100;
-                                                facing = Facing::Left;
-                                            }
-                                            _ => unreachable!(),
-                                        }
-                                    }
-                                    // 3 -> 4
-                                    100 => {
-                                        assert!((51..=100).contains(&point.col));
-                                        point.row += 1;
-                                    }
-                                    // 4 & 5
-                                    150 => {
-                                        match point.col {
-                                            // 5 -> 6
-                                            (1..=50) => {
-                                                point.row += 1;
-                                            }
-                                            // 4 -> 6
-                                            (51..=100) => {
-                                                point.row = point.col + 100;
-                                                point.col = 50;
-                                                facing = Facing::Left;
-                                            }
-                                            _ => unreachable!(),
-                                        }
-                                    }
-                                    // 6 -> 2
-                                    200 => {
-                                        assert!((1..=50).contains(&point.col));
-                                        point.col += 100;
-                                        point.row = 1;
-                                        facing = Facing::Down;
-                                    }
-                                    _ => unreachable!(),
-                                }
-                            }
-                        }
-                        Facing::Up => {
-                            // Easy path
-                            if (point.row - 1) % 50 != 0 {
-                                point.row -= 1;
-                            } else {
-                                // Cube calculations
-                                match point.row {
-                                    // 1 & 2
-                                    1 => {
-                                        match point.col {
-                                            // 1 -> 6
-                                            (51..=100) => {
-                                                point.row = point.col + 100;
-                                                point.col = 1;
-                                                facing = Facing::Right;
-                                            }
-                                            // 2 -> 6
-                                            (101..=150) => {
-                                                point.col -= 100;
-                                                point.row = 200;
-                                                facing = Facing::Up;
-                                            }
-                                            _ => unreachable!(),
-                                        }
-                                    }
-                                    // 3 -> 1
-                                    51 => {
-                                        assert!((51..=100).contains(&point.col));
-                                        point.row -= 1;
-                                    }
-                                    // 4 & 5
-                                    101 => {
-                                        match point.col {
-                                            // 5 -> 3
-                                            (1..=50) => {
-                                                point.row = point.col + 50;
-                                                point.col = 51;
-                                                facing = Facing::Right;
-                                            }
-                                            // 4 -> 3
-                                            (51..=100) => {
-                                                point.row -= 1;
-                                            }
-                                            _ => unreachable!(),
-                                        }
-                                    }
-                                    // 6 -> 5
-                                    151 => {
-                                        assert!((1..=50).contains(&point.col));
-                                        point.row -= 1;
-                                    }
-                                    _ => unreachable!(),
-                                }
-                            }
-                        }
-                    }
+                    let (point, facing) = match state.facing.step(state.pos) {
+                        Some(point) if map.contains_key(&point) => (point, state.facing),
+                        _ => cube.wrap(state.pos, state.facing),
+                    };
 
                     match map.get(&point).unwrap_or_else(|| panic!("{:?}", &point)) {
                         Tile::Open => {
@@ -511,10 +432,21 @@ impl Inst {
     }
 }
 
+/// Start on the leftmost open tile of the top row facing right.
+fn start_state(map: &BTreeMap<Point, Tile>) -> State {
+    let pos = map
+        .iter()
+        .find(|(point, tile)| point.row == 1 && matches!(tile, Tile::Open))
+        .map(|(point, _)| *point)
+        .unwrap();
+
+    State::new(pos, Facing::Right)
+}
+
 fn get_final_password(input: &str) -> usize {
     let note = Note::from(input);
 
-    let mut state = State::new(Point::new(1, 51), Facing::Right);
+    let mut state = start_state(&note.map);
 
     for ins in note.instructions.iter() {
         ins.apply(&mut state, &note.map);
@@ -526,10 +458,11 @@ fn get_final_password(input: &str) -> usize {
 fn get_final_password_cube(input: &str) -> usize {
     let note = Note::from(input);
 
-    let mut state = State::new(Point::new(1, 51), Facing::Right);
+    let cube = Cube::new(&note.map);
+    let mut state = start_state(&note.map);
 
     for ins in note.instructions.iter() {
-        ins.apply_cube(&mut state, &note.map);
+        ins.apply_cube(&mut state, &note.map, &cube);
     }
 
     1000 * state.pos.row + 4 * state.pos.col + state.facing as usize
@@ -576,5 +509,44 @@ mod test {
     #[test]
     fn test_() {
         assert_eq!(get_final_password(INPUT), 6032);
+        assert_eq!(get_final_password_cube(INPUT), 5031);
+    }
+
+    #[test]
+    fn test_cube_wrap() {
+        let note = Note::from(INPUT);
+        let cube = Cube::new(&note.map);
+        assert_eq!(cube.size, 4);
+
+        // Examples from the puzzle description
+        assert_eq!(
+            cube.wrap(Point::new(6, 12), Facing::Right),
+            (Point::new(9, 15), Facing::Down)
+        );
+        assert_eq!(
+            cube.wrap(Point::new(12, 11), Facing::Down),
+            (Point::new(8, 2), Facing::Up)
+        );
+        assert_eq!(
+            cube.wrap(Point::new(5, 7), Facing::Up),
+            (Point::new(3, 9), Facing::Right)
+        );
+
+        // Wrapping back has to return to the same tile.
+        for (&point, _) in note.map.iter() {
+            for facing in Facing::ALL {
+                if facing
+                    .step(point)
+                    .is_some_and(|p| note.map.contains_key(&p))
+                {
+                    continue;
+                }
+                let (next, next_facing) = cube.wrap(point, facing);
+                let back = next_facing.turn_left().turn_left();
+                let (prev, prev_facing) = cube.wrap(next, back);
+                assert_eq!(prev, point);
+                assert_eq!(prev_facing, facing.turn_left().turn_left());
+            }
+        }
     }
 }
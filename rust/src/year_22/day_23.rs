@@ -1,6 +1,10 @@
-use std::collections::HashSet;
+use rustc_hash::FxHashMap;
 
-use crate::utls::read_text_from_file;
+use crate::{
+    anim,
+    automaton::{ADJACENT, Pos, SparseGrid, Storage},
+    utls::read_text_from_file,
+};
 
 #[derive(Debug, Default, Clone, Copy, Eq, PartialEq, Hash)]
 struct Point {
@@ -12,6 +16,11 @@ impl Point {
     fn new(x: isize, y: isize) -> Self {
         Self { x, y }
     }
+
+    /// The cell in the automaton grid, where rows grow to the south.
+    fn cell(self) -> Pos {
+        (-self.y, self.x)
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
@@ -22,166 +31,186 @@ enum Dir {
     East = 3,
 }
 
+impl Dir {
+    /// The offset of the step in this direction.
+    fn step(self) -> Point {
+        match self {
+            Dir::North => Point::new(0, 1),
+            Dir::South => Point::new(0, -1),
+            Dir::West => Point::new(-1, 0),
+            Dir::East => Point::new(1, 0),
+        }
+    }
+
+    /// The offsets of the three neighbours which must be free to step in this direction.
+    fn side(self) -> [Point; 3] {
+        let Point { x, y } = self.step();
+        if x == 0 {
+            [-1, 0, 1].map(|dx| Point::new(dx, y))
+        } else {
+            [-1, 0, 1].map(|dy| Point::new(x, dy))
+        }
+    }
+}
+
+/// The positions of the elves in a sparse grid, making the neighbour checks constant time
+/// regardless of the count of the elves.
+/// A move depends on the proposals of the other elves, so the field shares the grid with the
+/// automata but not the update rule.
 #[derive(Debug, Clone)]
-struct Elf {
-    pos: Point,
-    propose: Option<Point>,
+struct ElfField {
+    grid: SparseGrid<bool>,
 }
 
-impl Elf {
-    fn new(pos: Point) -> Self {
-        let propose = None;
-        Self { pos, propose }
+impl ElfField {
+    fn new(elves: impl IntoIterator<Item = Point>) -> Self {
+        let grid = SparseGrid::from_cells(elves.into_iter().map(|elf| (elf.cell(), true)), false);
+        Self { grid }
     }
 
-    fn calc_moving_propose(&mut self, elves: &[Elf], dirs: &[Dir]) {
-        assert!(self.propose.is_none());
-        let north_free = elves
-            .iter()
-            .filter(|e| e.pos.y == self.pos.y + 1)
-            .all(|e| !(self.pos.x - 1..=self.pos.x + 1).contains(&e.pos.x));
+    fn is_elf(&self, pos: Point) -> bool {
+        self.grid.get(pos.cell())
+    }
 
-        let south_free = elves
+    fn elves(&self) -> impl Iterator<Item = Point> + '_ {
+        self.grid
             .iter()
-            .filter(|e| e.pos.y == self.pos.y - 1)
-            .all(|e| !(self.pos.x - 1..=self.pos.x + 1).contains(&e.pos.x));
+            .map(|((row, col), _)| Point::new(col, -row))
+    }
 
-        let west_free = elves
-            .iter()
-            .filter(|e| e.pos.x == self.pos.x - 1)
-            .all(|e| !(self.pos.y - 1..=self.pos.y + 1).contains(&e.pos.y));
+    fn elves_count(&self) -> usize {
+        self.elves().count()
+    }
 
-        let east_free = elves
-            .iter()
-            .filter(|e| e.pos.x == self.pos.x + 1)
-            .all(|e| !(self.pos.y - 1..=self.pos.y + 1).contains(&e.pos.y));
+    /// The position the elf proposes to move to, which is None if it has no neighbours or
+    /// can't move in any direction.
+    fn propose(&self, elf: Point, dirs: &[Dir]) -> Option<Point> {
+        // Each neighbour is looked up once since the sides of the directions overlap.
+        let mut neighbours = [[false; 3]; 3];
+        for (dx, dy) in ADJACENT {
+            neighbours[(dy + 1) as usize][(dx + 1) as usize] =
+                self.is_elf(Point::new(elf.x + dx, elf.y + dy));
+        }
+        if neighbours.iter().flatten().all(|occupied| !occupied) {
+            return None;
+        }
 
-        let all_free = north_free && south_free && west_free && east_free;
+        dirs.iter()
+            .find(|dir| {
+                dir.side()
+                    .iter()
+                    .all(|offset| !neighbours[(offset.y + 1) as usize][(offset.x + 1) as usize])
+            })
+            .map(|dir| {
+                let step = dir.step();
+                Point::new(elf.x + step.x, elf.y + step.y)
+            })
+    }
 
-        if all_free {
-            self.propose = None;
-        } else {
-            for dir in dirs.iter() {
-                match dir {
-                    Dir::North if north_free => {
-                        self.propose = Some(Point::new(self.pos.x, self.pos.y + 1));
-                        break;
-                    }
-                    Dir::South if south_free => {
-                        self.propose = Some(Point::new(self.pos.x, self.pos.y - 1));
-                        break;
-                    }
-                    Dir::West if west_free => {
-                        self.propose = Some(Point::new(self.pos.x - 1, self.pos.y));
-                        break;
-                    }
-                    Dir::East if east_free => {
-                        self.propose = Some(Point::new(self.pos.x + 1, self.pos.y));
-                        break;
-                    }
-                    _ => {}
-                };
+    /// Moves all the elves whose proposed position isn't proposed by any other elf.
+    /// Returns the count of the moved elves.
+    fn apply_round(&mut self, dirs: &[Dir]) -> usize {
+        let proposes: Vec<(Point, Point)> = self
+            .elves()
+            .filter_map(|elf| self.propose(elf, dirs).map(|target| (elf, target)))
+            .collect();
+
+        let mut targets_count: FxHashMap<Point, usize> = FxHashMap::default();
+        for (_, target) in &proposes {
+            *targets_count.entry(*target).or_default() += 1;
+        }
+
+        let mut moves = 0;
+        for (elf, target) in proposes {
+            if targets_count[&target] == 1 {
+                self.grid.set(elf.cell(), false);
+                self.grid.set(target.cell(), true);
+                moves += 1;
             }
         }
+
+        moves
     }
 
-    fn move_if_possible(&mut self, proposes: &[Point], duplicates: &mut HashSet<Point>) -> bool {
-        if let Some(propose) = self.propose.take() {
-            if !duplicates.contains(&propose) {
-                let propose_count = proposes
-                    .iter()
-                    .filter(|p| p.x == propose.x && p.y == propose.y)
-                    .count();
-                assert!(propose_count > 0);
+    /// The south west and north east corners of the smallest rectangle containing all elves.
+    fn bounding_box(&self) -> (Point, Point) {
+        let mut elves = self.elves();
+        let first = elves.next().expect("field must have elves");
 
-                if propose_count > 1 {
-                    duplicates.insert(propose);
-                    return false;
+        elves.fold((first, first), |(min, max), elf| {
+            (
+                Point::new(min.x.min(elf.x), min.y.min(elf.y)),
+                Point::new(max.x.max(elf.x), max.y.max(elf.y)),
+            )
+        })
+    }
+
+    /// The count of the empty ground tiles in the bounding box.
+    fn empty_tiles(&self) -> usize {
+        let (min, max) = self.bounding_box();
+        let width = max.x.abs_diff(min.x) + 1;
+        let height = max.y.abs_diff(min.y) + 1;
+
+        width * height - self.elves_count()
+    }
+
+    fn render(&self) -> String {
+        let (min, max) = self.bounding_box();
+
+        let mut frame = String::new();
+        // North is the positive direction of y.
+        for y in (min.y..=max.y).rev() {
+            for x in min.x..=max.x {
+                let ch = if self.is_elf(Point::new(x, y)) {
+                    '#'
                 } else {
-                    self.pos = propose;
-                    return true;
-                }
+                    '.'
+                };
+                frame.push(ch);
             }
+            frame.push('\n');
         }
 
-        false
+        frame
     }
 }
 
-fn fetch_input(input: &str) -> Vec<Elf> {
-    input
-        .lines()
-        .enumerate()
-        .flat_map(move |(y, line)| {
-            line.chars()
-                .enumerate()
-                .filter(|(_, ch)| *ch == '#')
-                .map(move |(x, _)| Elf::new(Point::new(x as isize, -(y as isize))))
-        })
-        .collect()
+fn fetch_input(input: &str) -> ElfField {
+    let elves = input.lines().enumerate().flat_map(move |(y, line)| {
+        line.chars()
+            .enumerate()
+            .filter(|(_, ch)| *ch == '#')
+            .map(move |(x, _)| Point::new(x as isize, -(y as isize)))
+    });
+
+    ElfField::new(elves)
 }
 
 fn calc_empty_tiles(input: &str) -> usize {
-    let mut elves = fetch_input(input);
+    let mut field = fetch_input(input);
 
     let mut prop_dir = vec![Dir::North, Dir::South, Dir::West, Dir::East];
 
-    for _ in 0..10 {
-        let clones = elves.clone();
-        elves
-            .iter_mut()
-            .for_each(|elf| elf.calc_moving_propose(&clones, &prop_dir));
-
-        let mut duplicates = HashSet::new();
-
-        let proposes: Vec<Point> = elves.iter().flat_map(|elf| elf.propose).collect();
-
-        elves.iter_mut().for_each(|elf| {
-            elf.move_if_possible(&proposes, &mut duplicates);
-        });
+    for round in 1..=10 {
+        field.apply_round(&prop_dir);
+        anim::frame(format_args!("Round {round}"), || field.render());
 
         prop_dir.rotate_left(1);
     }
 
-    let max_x = elves.iter().map(|elf| elf.pos.x).max().unwrap();
-    let min_x = elves.iter().map(|elf| elf.pos.x).min().unwrap();
-    let max_y = elves.iter().map(|elf| elf.pos.y).max().unwrap();
-    let min_y = elves.iter().map(|elf| elf.pos.y).min().unwrap();
-
-    let width = max_y.abs_diff(min_y);
-    let height = max_x.abs_diff(min_x);
-
-    (width + 1) * (height + 1) - elves.len()
+    field.empty_tiles()
 }
 
 fn find_first_no_move(input: &str) -> usize {
-    let mut elves = fetch_input(input);
+    let mut field = fetch_input(input);
 
     let mut prop_dir = vec![Dir::North, Dir::South, Dir::West, Dir::East];
 
     for round in 1.. {
-        let clones = elves.clone();
-        elves
-            .iter_mut()
-            .for_each(|elf| elf.calc_moving_propose(&clones, &prop_dir));
-
-        let mut duplicates = HashSet::new();
-
-        let proposes: Vec<Point> = elves.iter().flat_map(|elf| elf.propose).collect();
-
-        if proposes.is_empty() {
-            return round;
-        }
-
-        let moves = elves
-            .iter_mut()
-            .map(|elf| elf.move_if_possible(&proposes, &mut duplicates))
-            .filter(|m| *m)
-            .count();
-
-        if moves == 0 {
+        if field.apply_round(&prop_dir) == 0 {
             return round;
         }
+        anim::frame(format_args!("Round {round}"), || field.render());
 
         prop_dir.rotate_left(1);
     }
@@ -230,4 +259,21 @@ mod test {
     fn test_part_2() {
         assert_eq!(find_first_no_move(INPUT), 20);
     }
+
+    #[test]
+    fn test_field() {
+        let mut field = fetch_input(".....\n..##.\n..#..\n.....\n..##.\n.....");
+        assert_eq!(field.bounding_box(), (Point::new(2, -4), Point::new(3, -1)));
+        assert_eq!(field.empty_tiles(), 3);
+        assert_eq!(field.propose(Point::new(2, -2), &[Dir::North]), None);
+        assert_eq!(
+            field.propose(Point::new(2, -1), &[Dir::North, Dir::South]),
+            Some(Point::new(2, 0))
+        );
+
+        // The two elves in the south both want to move north and neither does.
+        let dirs = [Dir::North, Dir::South, Dir::West, Dir::East];
+        assert_eq!(field.apply_round(&dirs), 3);
+        assert_eq!(field.render(), "##\n..\n#.\n.#\n#.\n");
+    }
 }
@@ -1,6 +1,6 @@
 use std::collections::BTreeSet;
 
-use crate::utls::read_text_from_file;
+use crate::{anim, utls::read_text_from_file};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 enum Dir {
@@ -10,6 +10,17 @@ enum Dir {
     Right,
 }
 
+impl Dir {
+    fn get_char(self) -> char {
+        match self {
+            Dir::Up => '^',
+            Dir::Down => 'v',
+            Dir::Left => '<',
+            Dir::Right => '>',
+        }
+    }
+}
+
 impl From<char> for Dir {
     fn from(value: char) -> Self {
         match value {
@@ -125,6 +136,36 @@ fn fetch_blizzards(input: &str) -> (Vec<Blizzard>, usize, usize) {
     )
 }
 
+/// Renders the valley with the possible expedition positions as `E`. Cells with more than one
+/// blizzard show their count.
+fn render_valley(
+    blizzards: &[Blizzard],
+    positions: &BTreeSet<Point>,
+    width: usize,
+    height: usize,
+) -> String {
+    let mut frame = String::new();
+    for row in 0..height {
+        for col in 0..width {
+            let point = Point::new(row, col);
+            let mut here = blizzards.iter().filter(|b| b.pos == point);
+            let ch = match (here.next(), here.count()) {
+                (Some(blizzard), 0) => blizzard.dir.get_char(),
+                (Some(_), others) => char::from_digit(others as u32 + 1, 10).unwrap_or('*'),
+                (None, _) if positions.contains(&point) => 'E',
+                (None, _) if row == 0 && col == 1 => '.',
+                (None, _) if row == height - 1 && col == width - 2 => '.',
+                (None, _) if row == 0 || col == 0 || row == height - 1 || col == width - 1 => '#',
+                (None, _) => '.',
+            };
+            frame.push(ch);
+        }
+        frame.push('\n');
+    }
+
+    frame
+}
+
 fn calc_min_minutes(input: &str) -> usize {
     let (mut blizzards, height, width) = fetch_blizzards(input);
     let target = Point::new(height - 1, width - 2);
@@ -140,6 +181,9 @@ fn calc_min_minutes(input: &str) -> usize {
             .into_iter()
             .flat_map(|p| p.get_possible_poss(&bliz_set, width, height))
             .collect();
+        anim::frame(format_args!("Minute {mins}"), || {
+            render_valley(&blizzards, &current_poss, width, height)
+        });
 
         if current_poss.contains(&target) {
             return mins;
@@ -171,6 +215,9 @@ fn calc_min_minutes_repeated(input: &str) -> usize {
             .into_iter()
             .flat_map(|p| p.get_possible_poss(&bliz_set, width, height))
             .collect();
+        anim::frame(format_args!("Minute {mins}"), || {
+            render_valley(&blizzards, &current_poss, width, height)
+        });
 
         if current_poss.contains(&target) {
             match count {
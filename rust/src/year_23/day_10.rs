@@ -249,6 +249,66 @@ impl Maze {
         unreachable!("Must be solved before this point");
     }
 
+    /// Resolves the pipe under the start from the loop tiles connecting to it.
+    fn start_shape(&self, loop_poses: &HashSet<Pos>) -> char {
+        let connects = |pos: Option<Pos>, valid: &[char]| {
+            pos.filter(|pos| loop_poses.contains(pos))
+                .and_then(|pos| self.get_char(&pos))
+                .is_some_and(|ch| valid.contains(&ch))
+        };
+
+        let Pos { row, col } = self.start;
+        let up = connects(
+            row.checked_sub(1).map(|row| Pos::new(row, col)),
+            &['|', 'F', '7'],
+        );
+        let down = connects(Some(Pos::new(row + 1, col)), &['|', 'L', 'J']);
+        let left = connects(
+            col.checked_sub(1).map(|col| Pos::new(row, col)),
+            &['-', 'L', 'F'],
+        );
+        let right = connects(Some(Pos::new(row, col + 1)), &['-', 'J', '7']);
+
+        match (up, down, left, right) {
+            (true, true, _, _) => '|',
+            (_, _, true, true) => '-',
+            (true, _, true, _) => 'J',
+            (true, _, _, true) => 'L',
+            (_, true, true, _) => '7',
+            (_, true, _, true) => 'F',
+            invalid => unreachable!("Start can't be connected as {invalid:?}"),
+        }
+    }
+
+    /// Scans each row from the left, where crossing a loop pipe with a connection to the north
+    /// toggles between outside and inside. Horizontal runs of the loop are crossed only if they
+    /// leave in the opposite direction than they started, which is what this rule counts.
+    fn count_enclosed(&self) -> usize {
+        let loop_poses = self.get_loop_poses();
+        let start_shape = self.start_shape(&loop_poses);
+
+        let mut count = 0;
+        for (row, line) in self.cells.iter().enumerate() {
+            let mut inside = false;
+            for (col, &ch) in line.iter().enumerate() {
+                let pos = Pos::new(row, col);
+                if !loop_poses.contains(&pos) {
+                    if inside {
+                        count += 1;
+                    }
+                    continue;
+                }
+
+                let ch = if ch == 'S' { start_shape } else { ch };
+                if matches!(ch, '|' | 'L' | 'J') {
+                    inside = !inside;
+                }
+            }
+        }
+
+        count
+    }
+
     fn print_loop(&self) {
         let loop_poses = self.get_loop_poses();
         let lines: Vec<String> = self
@@ -289,11 +349,17 @@ impl Maze {
     }
 }
 
+fn count_enclosed(input: &str) -> usize {
+    let maze = Maze::from(input);
+    maze.count_enclosed()
+}
+
 fn calc_steps(input: &str) -> usize {
     let maze = Maze::from(input);
     maze.calc_steps()
 }
 
+#[allow(unused)]
 fn print_maze(input: &str) {
     let maze = Maze::from(input);
     maze.print_loop();
@@ -306,152 +372,8 @@ fn part_1(input: &str) {
 }
 
 fn part_2(input: &str) {
-    print_maze(input);
-    // After printing the maze I've checked the tiles manually and marked the wanted ones with t
-    // and the others with x
-    let maze = "
-xxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxx     0
-xxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxx╭─╮xxxxxxxxxxxxxxxxxxxxxxxxxxx╭╮xxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxx     1
-.................................................╭╯╭╯....................╭╮╭╮xx╭╯╰╮......................╭╮xxxxxx╭╮xxxxxxxxxxxxxxxxxxxxxxxxx     2
-.................................................╰╮╰╮╭╮╭╮................││││xx╰─╮╰╮...................╭─╯│╭╮╭─╮╭╯│xx.......................     3
-................................................╭╮╰╮││││╰╮...........╭╮╭─╯╰╯│xx╭╮│╭╯...................╰─╮│││╰╮╰╯╭╯╭╮x......................     4
-...............................................╭╯│╭╯╰╯╰╯╭╯...........││╰───╮╰╮╭╯╰╯│....................╭─╯│││x╰─╮│x│╰─╮.....................     5
-.............................................╭─╯╭╯╰╮╭───╯...........╭╯╰╮x╭─╯╭╯╰──╮╰╮╭╮.╭╮..............╰─╮││╰╮╭╮│╰╮│╭─╯.....................     6
-......................................╭╮xx╭─╮│╭╮│╭╮││╭╮.............╰─╮╰╮╰─╮│╭─╮x╰╮│││.││...........╭─╮╭─╯│╰╮│││╰╮╰╯╰─╮.....................     7
-.....................................╭╯│xx│╭╯╰╯│││││╰╯│.........╭╮╭╮╭╮╰╮│x╭╯╰╯╭╯xx││││╭╯╰╮.......╭╮x╰╮│╰─╮╰─╯│││╭╯╭╮╭─╯.....................     8
-............................╭╮.......╰╮╰╮╭╯╰╮x╭╯││╰╯╭─╯.........││││││╭╯╰╮│╭──╯╭╮╭╯││╰╯╭─╯......╭╯╰╮x││╭╮╰─╮╭╯│││╭╯╰╯╭╮.....................     9
-............................││.......╭╯╭╯╰╮╭╯╭╯╭╯╰─╮│╭──╮.....╭╮│││││││╭╮╰╯│╭╮x│││╭╯│╭─╯x╭╮╭╮xxx╰─╮│╭╯╰╯╰╮╭╯╰─╯│││╭──╯╰─╮x╭╮................    10
-.........................╭╮╭╯│.......╰╮│x╭╯╰╮╰╮╰╮╭╮│││╭─╯╭╮...│╰╯╰╯│││╰╯╰╮╭╯││╭╯╰╯╰─╯╰─╮x│╰╯│x╭╮╭╮││╰╮╭╮╭╯╰─╮╭─╯│││╭────╯╭╯│................    11
-.........................│││╭╯......╭─╯╰╮╰─╮│╭╯╭╯│╰╯││╰─╮││...╰───╮││╰─╮x│╰╮││╰─╮╭╮╭╮╭─╯x╰─╮╰─╯││╰╯╰╮╰╯││╭─╮││╭╮││││╭╮x╭╮╰╮│╭╮..............    12
-......................╭──╯││╰─╮╭╮.╭╮╰──╮╰──╯││╭╯x╰─╮╰╯╭╮╰╯╰─╮╭───╮││╰─╮╰╮│╭╯││x╭╯││││╰──╮╭╮╰╮╭─╯╰╮╭─╯x╭╯│╰╮╰╯││╰╯╰╯╰╯╰─╯╰─╯│││..............    13
-......................╰──╮│╰╮╭╯│╰─╯│╭─╮╰──╮╭╯│╰──╮x╰╮╭╯╰────╯╰──╮╰╯╰──╯╭╯│╰─╯╰╮╰╮│╰╯╰───╯││╭╯│x╭─╯╰╮╭╮│╭╯x╰─╮╰╯╭─╮╭─╮╭─╮╭──╯│╰╮.............    14
-.......................╭─╯╰─╯│x╰─╮╭╯╰╮╰───╯│╭╯╭──╯╭╮│╰─╮x╭╮x╭╮╭╮│╭──╮╭╮╰╮╰───╮│╭╯╰╮╭╮╭╮xx││╰╮╰╮╰──╮││││╰╮╭──╯╭╮╰╮││╭╯│╭╯╰╮╭─╯╭╯.............    15
-.......................╰╮╭──╮╰╮╭╮││╭╮╰────╮│╰╮│╭╮x││╰─╮╰╮│╰╮││││╰╯╭─╯│╰╮│╭──╮││╰╮╭╯│╰╯╰╮╭╯│╭╯╭╯x╭─╯╰╯││╭╯╰──╮│╰─╯╰╯╰─╯╰──╯│╭─╯..............    16
-........................╰╯╭╮╰╮││││╰╯│╭╮╭─╮││x│││╰╮│╰──╯╭╯│╭╯│╰╯╰╮x╰─╮╰╮╰╯╰─╮╰╯╰╮│╰╮│╭──╯╰╮│╰╮│╭╮╰───╮│││x╭╮╭╯╰─────────╮╭╮│╰───╮............    17
-.......................╭─╮││╭╯││╰╯╭─╯││╰╮╰╯│╭╯╰╯╭╯╰─╮╭─╯╭╯╰╮╰╮╭─╯x╭╮╰╮│╭──╮╰──╮││╭╯│╰──╮╭╯╰─╯╰╯╰╮╭─╮│││╰╮│││╭──────────╯│││╭───╯............    18
-.....................╭╮╰╮╰╯││╭╯╰─╮│╭╮││╭╯╭─╯╰──╮╰─╮╭╯╰╮x╰╮╭╯╭╯╰─╮╭╯╰╮│││╭─╯╭╮╭╯│││x│╭──╯╰─╮╭─╮╭─╯╰╮╰╯│╰╮╰╯│││x╭╮╭╮x╭╮╭╮╭╯╰╯╰───╮............    19
-....................╭╯╰╮╰─╮││╰╮╭─╯│││││╰╮│x╭╮╭─╯╭─╯╰─╮╰╮╭╯╰╮│╭╮╭╯╰─╮╰╯╰╯╰──╯│╰╮││╰╮││╭╮x╭╮╰╯╭╯│╭╮x╰─╮│x│╭╮│││╭╯╰╯╰╮││││╰─╮╭────╯............    20
-....................╰─╮╰──╯││╭╯╰─╮╰╯╰╯╰╮│╰╮││╰─╮╰─╮╭╮│╭╯│╭─╯││╰╯╭╮x│╭╮╭╮╭╮╭╮╰─╯│╰╮╰╯╰╯╰─╯╰╮╭╯╭╯││x╭─╯╰╮╰╯│││││╭───╯││││╭╮││╭╮xx╭──╮.........    21
-....................╭─╯╭──╮│││╭╮x╰────╮││╭╯│╰╮╭╯╭─╯│╰╯╰╮││╭╮│╰╮╭╯╰╮╰╯╰╯││╰╯╰──╮│╭╯╭╮╭╮╭─╮╭╯╰╮│x│╰╮╰─╮╭╯x╭╯││╰╯╰╮╭──╯││╰╯││╰╯╰─╮╰─╮╰╮........    22
-..................╭╮╰──╯╭╮││││││╭─╮╭╮╭╯╰╯╰╮│╭╯╰╮╰╮x╰─╮╭╯│││││╭╯╰─╮│╭╮╭╮││x╭──╮│╰╯╭╯╰╯│╰╮╰╯╭─╯│╭╯╭╯╭╮││╭╮╰╮│╰╮╭─╯│╭──╯│╭─╯│╭──╮╰╮╭╯╭╯........    23
-.............╭╮x╭╮│╰╮╭──╯╰╯╰╯╰╯│╰╮││││╭──╮╰╯╰─╮│╭╯╭╮x│╰─╯╰╯╰╯╰╮╭╮│╰╯│││╰╯╭╯╭╮│╰╮╭╯x╭╮╰─╯╭╮╰─╮││╭╯╭╯╰╯│││╭╯│╭╯╰──╯╰──╮│╰──╯│╭╮╰─╯╰╮╰─╮.......    24
-..........╭──╯│x│╰╯╭╯╰────────╮│╭╯╰╯│╰╯╭╮╰╮╭──╯│╰╮│╰╮│╭───────╯││╰╮╭╯│╰─╮╰╮││╰╮││╭╮│╰──╮││╭╮│││╰╮╰──╮││╰╯╭╯╰╮╭───╮╭─╯│╭───╯││╭╮╭─╯╭╮│.......    25
-..........╰──╮╰╮╰─╮╰──╮╭╮╭╮x╭─╯│╰──╮│╭╮│╰─╯│╭╮x╰╮╰╯╭╯││╭╮╭╮╭─╮x││╭╯│╭╯╭╮│╭╯│╰╮╰╯╰╯││╭──╯││││││╰╮╰╮╭─╯│╰─╮│x╭╯╰─╮x╰╯╭╮│╰╮╭╮x│╰╯╰╯╭╮│╰╯.......    26
-..........╭─╮│╭╯╭╮╰──╮╰╯╰╯╰╮╰─╮│╭╮╭╯╰╯│╰──╮││╰╮x╰─╮╰╮│╰╯│││╰╮╰╮││╰╮││╭╯╰╯╰╮│x╰─╮╭─╯││╭╮x││││││╭╯╭╯╰─╮│x╭╯╰╮╰╮╭─╯╭──╯│╰╮│││╭╯╭╮╭╮│╰╯.........    27
-..........╰╮╰╯│x│╰╮╭╮╰────╮╰─╮│╰╯│╰─╮╭╯╭╮╭╯││╭╯╭──╯╭╯│╭─╯│╰╮╰╮│││╭╯││╰╮x╭╮╰╯╭╮╭╯╰╮╭╯╰╯╰╮││││││╰╮╰─╮╭╯╰─╯╭╮│╭╯╰─╮╰─╮╭╯╭╯╰╯╰╯╭╯╰╯╰╯...........    28
-........╭╮x╰─╮╰╮╰╮│││x╭╮╭╮╰─╮││╭─╯╭╮││╭╯│╰╮╰╯╰╮╰──╮│╭╯│╭╮╰╮╰╮││││╰╮│╰╮╰╮││x╭╯│╰╮╭╯╰╮╭╮╭╯│││││╰╮│╭─╯╰──╮╭╯│││╭──╯╭╮││╭╯╭────╯╭╮..............    29
-.......╭╯╰╮╭╮╰╮│╭╯│││╭╯╰╯│╭╮│││╰╮╭╯│││╰╮│╭╯╭──╯x╭╮│││╭╯││x│╭╯│╰╯│╭╯╰╮│╭╯││╭╯╭╯x│╰─╮││╰╯╭╯╰╯╰╯╭╯││╭╮╭╮x││x││││╭╮x││││╰╮│╭╮╭╮╭╯╰─╮............    30
-.......╰─╮╰╯╰─╯│╰╮╰╯╰╯╭─╮││╰╯││╭╯╰╮╰╯│╭╯││╭╯x╭─╮│╰╯╰╯│╭╯╰╮││x╰─╮││╭╮││╰╮││╰╮╰╮╭╯╭╮││╰─╮│╭───╮│╭╯││││╰╮│╰╮╰╯│││╰─╯││╰─╯╰╯╰╯╰╯╭──╯............    31
-.....╭──╮╰────╮│x╰────╯╭╯╰╯╭╮│││╭╮╰─╮│╰╮╰╯│╭╮╰╮│╰───╮│╰╮╭╯││╭─╮│╰╯│╰╯╰╮│││╭╯╭╯╰╮│╰╯│╭─╯╰╯╭╮x││╰╮││││╭╯│╭╯╭╮│╰╯╭╮╭╯╰╮╭╮╭╮╭──╮╰─╮╭╮╭╮.........    32
-.....│╭╮╰─╮╭──╯│╭─╮╭─╮x╰───╯││││││╭─╯╰╮╰─╮││╰╮││╭─╮╭╯╰─╯╰╮│││╭╯╰─╮╰╮╭╮│││││╭╯╭─╯╰╮╭╯╰╮╭╮╭╯╰╮│╰╮│││││╰╮││╭╯││╭─╯╰╯╭╮││╰╯╰╯╭╮│╭─╯││││.........    33
-.....╰╯│╭╮╰╯╭─╮╰╯╭╯╰╮╰─╮╭╮╭╮│││╰╯│╰─╮╭╯╭╮│││╭╯││╰╮│╰──╮╭─╯││││x╭╮│╭╯││││││││x╰─╮╭╯│╭─╯││╰╮╭╯│╭╯││││╰╮││││╭╯│╰╮x╭─╯╰╯╰────╯││╰──╯╰╯│.........    34
-.......│││╭─╯x╰─╮│╭╮│╭─╯│╰╯│││╰─╮│x╭╯╰╮│╰╯│││╭╯╰╮│╰╮╭─╯│╭╮│╰╯│╭╯│││╭╯││││││╰╮╭╮│╰╮││╭╮││╭╯╰╮│╰╮││││╭╯││││╰╮│╭╯╭╯╭╮╭╮╭──╮╭╮│╰─╮╭╮╭─╯.........    35
-.......╰╯╰╯╭╮╭╮╭╯╰╯╰╯╰─╮╰─╮╰╯╰──╯╰─╯╭─╯╰╮╭╯││╰╮t││t╰╯╭─╯│││t╭╯│╭╯│││t╰╯││╰╯╭╯│││╭╯│││╰╯││╭─╯╰╮││││╰╯t╰╯││╭╯│╰─╯╭╯││╰╯╭╮╰╯│╰─╮╰╯╰╯...........    36
-......╭╮╭╮╭╯╰╯╰╯╭╮╭╮╭─╮╰──╯╭╮╭╮╭╮╭─╮╰─╮╭╯╰╮││╭╯╭╯╰──╮╰╮╭╯│╰╮╰╮││╭╯│╰─╮╭╯╰╮╭╯╭╯╰╯╰╮││╰─╮││╰╮╭╮│╰╯│╰──╮t╭╯││x│╭╮╭╯╭╯╰──╯╰─╮│╭╮╰──╮............    37
-....╭─╯╰╯╰╯╭╮╭─╮│╰╯││x╰╮╭╮╭╯││││╰╯x╰╮╭╯╰╮t│││╰╮│╭╮╭─╯t││╭╯╭╯t││╰╯╭╯╭╮││╭─╯│╭╯t╭──╯││╭╮│││╭╯│││╭─╯╭─╮╰╮╰─╯╰─╯│╰╯x╰╮╭╮╭───╯╰╯│╭╮╭╯............    38
-....╰╮╭─╮╭─╯╰╯x╰╯╭╮╰╯╭─╯│││╭╯│╰╯╭╮╭─╯╰─╮╰╮╰╯│╭╯││││╭──╯│╰╮│╭─╯╰╮╭╯╭╯╰╯│╰╮╭╯╰─╮│╭─╮│││││││╰╮││││╭╮╰╮│╭╯t╭───╮╰─╮xx╰╯│╰─────╮││││x╭─╮.........    39
-.....╰╯╭╯│╭───╮╭╮││╭╮╰──╯││╰─╯╭─╯│╰──╮t╰╮╰─╮╰╯t│││││╭╮╭╯╭╯│╰╮╭╮│╰╮╰─╮t│╭╯╰╮╭╮││╰╮││││╰╯│╰╮│││││││╭╯││╭╮╰─╮x╰─╮╰╮╭╮╭╯╭─────╯╰╯│╰─╯╭╯.........    40
-.....╭─╯╭╯╰──╮╰╯╰╯╰╯│╭╮╭╮╰╯╭─╮╰─╮╰───╯╭╮╰╮╭╯╭──╯│╰╯││││t╰╮│t╰╯││t│╭╮│╭╯│╭╮╰╯│││╭╯│││╰─╮│╭╯││╰╯││││t╰╯││╭╮│╭╮x╰╮╰╯│╰╮╰───╮╭╮╭╮│╭─╮╰─╮........    41
-.....╰──╯╭───╯╭╮╭──╮╰╯╰╯╰╮x╰╮│╭╮╰╮╭───╯╰╮╰╯t╰──╮╰─╮╰╯││╭╮│╰╮╭─╯╰╮││╰╯╰╮╰╯│t╭╯││╰╮││╰╮╭╯││t│╰╮╭╯│││╭──╯╰╯│╰╯╰─╮╰╮╭╯╭╯╭╮╭╮╰╯│││╰╯x│╭─╯........    42
-.........│╭──╮│╰╯╭╮│╭───╮╰──╯╰╯│x╰╯╭─╮╭╮╰────╮╭╯╭─╯╭╮│││╰╯╭╯╰╮╭╮││╰──╮╰╮╭╯╭╯╭╯╰╮│╰╯╭╯╰╮│╰╮│╭╯╰╮││││╭╮╭─╮╰──╮╭╯x│╰╮╰─╯╰╯│╭─╯│╰──╮╰╯..........    43
-.........╰╯╭─╯│x╭╯╰╯╰╮╭─╯╭──╮╭╮╰───╯t╰╯│╭╮╭──╯╰╮╰─╮││││╰╮╭╯t╭╯│╰╯╰╮╭╮╰╮││t╰╮╰╮╭╯╰─╮╰─╮││╭╯│╰╮t╰╯╰╯╰╯╰╯t╰──╮│╰╮╭╯╭╯╭──╮x╰╯╭─╯╭─╮╰╮...........    44
-..........╭╯╭╮╰╮│╭╮╭─╯╰──╯╭╮╰╯╰──╮╭───╮╰╯│╰╮t╭╮│╭╮╰╯│╰╯╭╯╰─╮╰╮╰─╮╭╯│╰─╯│╰─╮│╭╯│╭─╮╰╮╭╯│││t╰─╯╭────╮t╭╮╭───╯╰─╯╰╮╰─╯╭╮│╭──╯╭╮╰╮╰─╯...........    45
-..........╰─╯╰╮│╰╯╰╯╭─────╯╰─────╯│╭──╯╭╮╰─╯╭╯╰╯│╰─╮╰╮t╰╮╭╮╰╮│╭─╯╰╮╰╮╭╮╰─╮│││t│╰╮│╭╯╰╮││╰─╮╭─╯╭╮╭╮│╭╯╰╯╭──────╮╰───╯╰╯│╭──╯╰─╯╭──╮..........    46
-..............╰╯╭╮╭╮╰─╮╭─╮╭─╮╭─╮╭─╯╰╮╭╮│╰╮╭─╯╭╮╭╯t╭╯╭╯╭─╯│╰╮│╰╯╭╮t╰╮╰╯╰╮╭╯││╰╮╰╮││╰╮t╰╯│╭─╯│╭╮││││╰╯╭─╮│╭─────╯╭╮x╭╮╭─╯╰───╮╭─╯╭─╯..........    47
-.............╭──╯╰╯╰╮x││x╰╯x╰╯╭╯│╭──╯│╰╯╭╯╰─╮││╰╮╭╯╭╯t│╭╮│╭╯╰──╯╰─╮╰╮╭╮││╭╯│╭╯t││╰─╯╭──╯╰─╮╰╯╰╯╰╯╰──╯╭╯│╰╮╭╮╭╮x│╰╮│╰╯╭─────╯╰─╮╰╮.╭╮........    48
-.............╰─────╮╰╮╰╯╭───╮x╰─╯╰───╯╭─╯╭╮╭╯││╭╯╰╮╰─╮││││╰╮╭─╮╭──╯╭╯││││╰╮│╰─╮╰╯t╭─╯╭╮╭╮╭╯t╭╮╭╮╭────╯╭╯t╰╯╰╯╰╮│╭╯│╭─╯╭╮╭╮╭─╮╭╯╭╯╭╯╰╮.......    49
-.............╭──╮╭╮╰╮╰─╮╰──╮╰─╮╭──────╯╭╮││╰╮│││t╭╯╭╮││││╰╮│╰╮│╰─╮╭╯╭╯│││╭╯│╭╮╰──╮│╭╮│││││╭╮│╰╯│╰╮╭──╮│╭──╮╭─╮╰╯╰─╯╰─╮││││╰╮││╭╯.│╭─╯.......    50
-...........╭─╯╭╮╰╯╰─╯╭╮╰───╯╭╮╰╯╭╮╭────╯╰╯╰╮╰╯╰╯╭╯╭╯││││╰╮││╭╯│╭─╯│╭╯t╰╯││╭╯│╰──╮││││││││││╰╯╭╮╰╮╰╯╭╮╰╯╰─╮│╰╮╰╮╭╮╭╮╭─╯│╰╯╰╮││││╭─╯│.........    51
-...........╰──╯╰─────╯╰─────╯│╭╮│││╭───────╯╭╮╭╮╰─╯t╰╯││╭╯││╰╮││╭╮││╭╮t╭╯│╰╮╰──╮││││││││││╰╮╭╯╰╮╰╮╭╯╰────╯╰─╯x│││││╰╮x│╭──╯│││╰╯╭─╯.........    52
-..............╭╮╭╮╭╮╭─╮╭╮╭╮╭╮╰╯╰╯╰╯╰────╮╭╮╭╯││╰─────╮│││╭╯╰╮││╰╯││││╰╮╰╮╰╮╰╮╭─╯╰╯││││││││t││╭╮╰╮│╰──╮╭╮╭╮╭╮╭╮╰╯╰╯╰─╯╭╯╰╮╭╮│╰╯╭╮╰──╮........    53
-.............╭╯╰╯╰╯│╰╮││╰╯╰╯╰──╮╭╮x╭───╮╰╯││╭╯│╭─────╯╰╯╰╯t╭╯│╰╮╭╯│╰╯╭╯t╰─╯t│╰─╮t╭╯│╰╯╰╯││╭╯││╰─╯╰───╯│╰╯╰╯╰╯╰─╮╭╮╭──╯╭╮╰╯╰╯╭─╯╰╮╭╮│........    54
-.............╰────╮╰─╯│╰──╮╭╮╭╮╰╯│╭╯t╭╮╰─╮╰╯╰─╯╰────╮╭─╮╭──╯╭╯╭╯╰╮╰╮╭╯t╭────╯╭─╯t│╭╯t╭╮╭╯││╭╯╰────╮╭╮╭╯╭╮╭─────╯│││╭──╯╰╮╭──╯╭╮x╰╯╰╯........    55
-.........╭╮╭─╮╭╮╭╮╰╮╭╮╰─╮x╰╯╰╯╰─╮│╰──╯╰─╮╰╮╭─╮╭╮╭───╯│╭╯╰─╮╭╯t╰╮╭╯╭╯╰╮╭╯╭╮╭─╮╰─╮t╰╯╭─╯│╰─╯╰╯╭─────╯│││╭╯╰╯x╭╮x╭╮│╰╯╰╮╭╮x╰╯╭──╯╰╮╭╮..........    56
-.........││╰╮││││╰─╯│╰─╮│╭╮╭╮╭──╯╰──╮╭──╯╭╯╰╮│││╰────╯╰╮╭─╯╰──╮││t╰╮╭╯╰─╯││╭╯╭╮╰─╮t╰─╮│╭╮╭─╮╰────╮╭╯╰╯╰────╯╰╮│││╭──╯││╭──╯╭╮╭╮╰╯│..........    57
-........╭╯╰─╯╰╯╰╯╭──╯╭╮│││││╰╯╭╮╭──╮│╰──╮│╭╮│╰╯╰──╮╭──╮│╰─╮╭╮╭╯││╭─╯│tttt││╰╮││╭─╯╭─╮│╰╯╰╯╭╯t╭╮╭╮╰╯╭────────╮╰╯╰╯╰─╮x│╰╯╭──╯╰╯│╭─╯..........    58
-........╰────────╯x╭─╯╰╯╰╯╰╯╭╮│││╭─╯╰───╯╰╯│╰────╮│╰─╮│╰╮╭╯│╰╯t╰╯│╭╮╰─╮tt││╭╯│││╭╮╰╮│╰───╮╰╮╭╯╰╯╰─╮╰─╮╭──╮╭╮╰──╮╭──╯╭╯╭╮╰────╮││x╭─╮........    59
-........╭─────────╮╰─╮╭─╮╭─╮│╰╯╰╯╰─╮t╭──╮╭╮╰─╮╭─╮││╭╮││╭╯╰╮╰──╮tt││╰╮╭╯tt╰╯╰─╯╰╯│╰─╯│╭╮╭╮╰╮│╰──╮╭╮│╭╮╰╯╭─╯││╭╮x╰╯╭╮x╰╮│╰───╮╭╯╰╯x│╭╯........    60
-........╰──╮╭╮╭──╮╰──╯╰╮╰╯╭╯╰─╮╭───╯╭╯╭╮╰╯╰─╮│╰╮││╰╯╰╯╰╯tt╰╮╭╮╰─╮││t││ttttttttt╭╯╭─╮││╰╯╰╮││╭─╮╰╯│╰╯│╭─╯╭─╯╰╯│╭╮╭╯╰──╯╰╮╭╮x│╰╮╭╮╭╯╰─╮.......    61
-...........╰╯╰╯x╭╯╭╮╭─╮╰─╮╰──╮│╰────╯╭╯╰─╮╭╮│╰─╯╰╯╭───╮t╭─╮││╰╮╭╯╰╯t││ttttttttt│╭╯t│╰╯╭─╮╰╯││╭╯╭╮╰─╮│╰──╯╭╮╭╮╰╯╰╯╭──╮╭╮╰╯╰╮╰─╯│╰╯╭──╯╭╮╭─╮..    62
-.............╭─╮│╭╯╰╯x╰──╯╭─╮╰╯╭╮╭╮╭╮╰──╮││││╭────╯╭─╮╰╮╰╮│╰╯t┼│tttt╰╯ttttttttt╰╯╭─╯╭╮╰╮╰─╮╰╯╰╮││╭─╯╰────╯╰╯╰─╮╭╮╰╮x╰╯╰──╮╰─╮x╰─╮│╭╮x│╰╯╭╯..    63
-.............╰╮╰╯│╭───────╯╭╯╭╮│││╰╯╰───╯╰╯╰╯│╭──╮╭╯t╰╮╰─╯╰╮tt││ttttttttttttttt╭─╯╭─╯╰╮│╭╮╰──╮│││╰────────╮╭─╮╰╯╰─╯╭────╮│╭─╯╭╮x│││╰╮│╭─╯...    64
-..............╰╮╭╯╰───────╮│x││││╰─────────╮╭╯╰─╮╰╯╭╮╭╯╭╮╭╮│tt││ttttttttttttttt╰──╯t╭╮╰╯││╭──╯││╰─────────╯│╭╯x╭─╮╭╯╭╮╭─╯│╰──╯╰─╯╰╯╭╯││╭╮...    65
-.............╭─╯│x╭───╮╭──╯╰─╯╰╯╰╮x╭────╮╭─╯╰╮╭╮╰──╯│╰─╯╰╯││tt╰╯ttttttttttttttt╭────╯│╭╮││╰╮╭╮╰╯╭─╮╭╮╭─────╯╰──╯╭╯│╭╯╰╯╭─╯╭──╮╭╮╭╮╭╯x│╰╯│...    66
-.............╰──╯x╰──╮│╰╮╭─╮╭─╮╭╮╰─╯╭╮╭╮╰╯╭╮t││╰╮╭─╮╰─╮╭─╮╰╯ttttttttttttttttttt╰────╮╰╯╰╯╰╮╰╯╰╮╭╯t╰╯╰╯╭╮╭────╮╭╮╰─╯╰──╮│╭╮╰╮╭╯│╰╯│╰──╯╭╮│...    67
-...............╭╮x╭──╯╰─╯╰╮╰╯x╰╯╰╮╭╮│││╰──╯╰─╯╰╮╰╯t╰──╯│╭╯ttttttttttttttttttttttt╭──╯╭───╮╰───╯│╭──╮╭─╯│╰───╮╰╯│╭╮╭╮╭╮│││╰╮│╰╮╰─╮╰────╯││...    68
-...............│╰╮╰──╮╭─╮╭╯╭─╮╭─╮╰╯╰╯╰╯╭──────╮╰╮╭╮t╭╮t││╭╮ttttttttttttttttttttt╭╯╭──╯t╭─╯╭─╮╭╮│╰─╮│╰─╮╰───╮╰─╮╰╯╰╯╰╯╰╯╰╯x╰╯x╰─╮╰────╮.╰╯...    69
-...............╰╮╰─╮╭╯│x╰╯x╰╮│╰╮│╭────╮╰─────╮╰╮╰╯╰─╯╰╮│╰╯╰╮tttttttttttttttttttt╰─╯╭──╮╰──╯t╰╯╰╯╭╮││╭╮╰╮╭╮x╰─╮╰╮╭╮╭─╮x╭╮╭╮╭╮╭─╮╰╮╭─╮╭╯......    70
-................╰─╮╰╯╭╯╭─╮x╭╯╰╮││╰───╮╰──────╯t│╭─────╯│╭──╯ttttttttttttttttttttttt╰─╮╰────╮╭╮t╭╯╰╯╰╯╰╮╰╯╰──╮│╭╯│││╭╯╭╯││╰╯╰╯╭╯x╰╯x││.......    71
-..............╭──╮╰──╯x╰╮│╭╯╭╮╰╯│╭╮╭╮╰───╮╭╮t╭╮│╰────╮t│╰╮╭╮tttttttttttttttttttttt╭╮t╰────╮╰╯╰─╯╭───╮╭╯╭╮╭──╯│╰─╯╰╯│x│╭╯╰╮╭──╯x╭──╮││.......    72
-..............╰─╮╰─╮╭─╮╭╯││╭╯╰─╮╰╯╰╯╰─╮╭╮╰╯╰─╯│╰─────╯╭╯╭╯││╭╮tt╭─╮tttttttttttttt╭╯╰────╮t╰─╮╭─╮│╭──╯╰─╯╰╯╭╮x│╭╮╭─╮╰─╯╰─╮││╭╮╭╮│╭─╯│╰╮......    73
-.............╭──╯╭╮│╰╮│╰╮│╰╯╭╮╭╯╭──╮╭╮╰╯╰──╮╭╮╰─╮╭─╮╭╮╰╮╰╮││││╭╮│╭╯tttttttttttttt╰─╮╭╮╭╮╰───╯╰╮╰╯│╭─╮╭╮╭──╯╰╮╰╯│╰╮│╭─╮╭─╯│╰╯╰╯╰╯╰╮╭╯╭╯......    74
-.............╰───╯│╰─╯╰─╯│╭─╯╰╯╭╯╭─╯│╰────╮│││╭╮│╰╮│││t│╭╯││││││││╭╮╭╮ttttttt╭╮tttt╰╯╰╯╰─╮╭╮╭╮╰╮╭╯│t╰╯╰╯╭──╮╰─╮│╭╯│╰╮│╰──╯╭╮╭─╮╭─╯╰─╯.......    75
-.............╭────╯╭╮╭╮╭╮│╰───╮│╭╯╭╮│╭────╯╰╯╰╯╰╯╭╯╰╯╰─╯╰─╯╰╯││││╰╯╰╯╰╮tttttt││t╭╮╭╮╭──╮╭╯│╰╯╰─╯╰─╯╭╮╭╮t╰─╮│╭─╯╰╯╭╯╭╯│╭─╮╭╯│╰╮│╰╮x╭╮x.......    76
-.............╰─────╯╰╯╰╯│╰────╯│╰─╯╰╯╰─────╮t╭╮t╭╯╭╮╭╮╭──╮╭╮╭╯│╰╯╭─╮╭╮╰─╮╭╮t╭╯╰─╯││╰╯╭─╯╰─╯t╭──────╯│││╭╮╭╯│╰──╮x╰─╯x││x╰╯x╰─╯╰─╯╭╯╰──╮.....    77
-..........╭╮..╭╮╭╮╭───╮╭╯╭╮╭──╮│╭──────╮╭──╯╭╯│╭╯╭╯╰╯╰╯╭╮╰╯╰╯t╰╮╭╯t╰╯╰──╯│╰╮╰──╮╭╯╰╮╭╯╭╮╭╮╭╮╰─╮╭╮╭─╮╰╯╰╯╰╯x╰──╮╰╮╭╮x╭╯│╭─────╮╭╮x│╭──╮╰╮....    78
-.........╭╯╰╮.││││╰──╮╰╯╭╯╰╯╭╮╰╯│╭─╮╭─╮╰╯╭╮t│╭╯│╭╯t╭╮╭─╯╰─╮t╭╮t││╭╮t╭╮╭─╮│╭╯╭╮╭╯╰─╮│╰╮│╰╯│││╭╮╰╯╰╯t╰─────╮╭──╮╰╮╰╯│╭╯╭╯│╭────╯│╰╮││╭╮╰╮│....    79
-.........╰─╮╰─╯│││╭╮x╰─╮│╭──╯│╭╮╰╯x╰╯╭╯╭─╯╰╮│╰─╯╰──╯╰╯╭──╮╰╮││╭╯╰╯╰─╯╰╯╭╯││╭╯│╰──╮││╭╯╰─╮│││││╭╮╭╮╭╮t╭╮╭╮╰╯╭─╯╭╯╭─╯╰─╯x││╭╮╭╮x│╭╯│╰╯╰╮╰╯....    80
-...........╰╮╭╮╰╯╰╯│╭╮x╰╯╰──╮╰╯╰─────╯t│╭──╯│╭──╮╭────╯╭─╯╭╯││╰─╮╭─────╯╭╯││╭╯t╭─╯│││╭╮╭╯╰╯╰╯╰╯╰╯╰╯╰─╯││╰──╯╭╮╰─╯╭╮╭╮x╭╯╰╯╰╯╰╮││x│╭──╯╭─╮...    81
-..........╭╮╰╯╰─╮╭╮╰╯│╭─────╯╭╮╭───╮╭╮╭╯│╭╮t│╰─╮│╰────╮╰─╮╰╮││╭─╯│t╭─╮╭╮╰╮╰╯╰╮╭╯╭╮││││╰╯╭────────╮╭╮╭╮╰╯╭─╮x││╭─╮││││╭╯╭╮╭╮╭╮╰╯╰─╯╰─╮╭╯╭╯...    82
-..........│╰╮.╭─╯│╰─╮╰╯╭╮╭╮╭╮│╰╯x╭─╯││╰╮│││╭╯╭╮││╭─╮╭─╯╭╮╰─╯││╰─╮╰╮│╭╯│╰╮│╭──╯╰─╯││││╰──╯╭───────╯│╰╯╰──╯╭╯╭╯╰╯╭╯│││││╭╯╰╯││╰─╮╭─╮╭─╯│╭╯....    83
-........╭╮╰╮╰─╯╭─╯╭─╯╭─╯││╰╯╰╯x╭╮╰──╯╰╮│││││╭╯╰╯╰╯t╰╯╭╮││t╭╮│╰╮t╰╮││╰╮╰╮│││╭╮t╭╮╭╯││╰──╮╭╯╭────╮╭─╯╭─╮╭──╯╭╯╭─╮╰─╯╰╯╰╯╰─╮x╰╯x╭╯╰╮╰╯╭─╯╰─╮╭╮.    84
-......╭─╯│x╰╮╭╮│╭─╯╭╮│╭─╯│╭────╯╰─╮╭─╮╰╯││╰╯╰─╮╭──╮╭─╯╰╯│╭╯││╭╯╭╮│││╭╯╭╯╰╯╰╯│╭╯╰╯╭╯│╭╮╭╯╰─╯╭─╮╭╯│╭╮╰╮│╰───╯╭╯x│╭╮╭─╮╭╮╭╮╰───╮│╭╮╰──╯╭───╯││.    85
-......╰─╮╰─╮╰╯╰╯╰──╯╰╯╰─╮││╭─────╮╰╯t│╭╮╰╯╭───╯│╭─╯╰╮╭─╮│╰╮╰╯╰─╯╰╯╰╯╰╮╰╮╭───╯╰──╮╰╮││╰╯╭╮╭─╯╭╯╰╮│││╭╯│╭╮╭──╯╭╮╰╯╰╯╭╯│││╰───╮││││╭──╮╰────╯╰╮    86
-........╰─╮╰╮╭────────╮x││╰╯╭─╮╭╮╰──╮││╰─╮│t╭╮╭╯╰───╯╰╮╰╯t╰─╮╭╮╭──╮╭╮│╭╯╰─╮╭────╯╭╯│╰╮╭╯│╰─╮╰─╮╰╯││╰╮│││╰───╯╰───╮╰╮││╰───╮╰╯│││╰─╮│╭─╮╭─╮╭╯    87
-....╭─────╯╭╯╰─────╮╭╮╰╮╰╯╭╮╰╮││╰───╯╰╯╭─╯╰─╯╰╯╭─────╮╰╮t╭─╮╰╯╰╯╭╮││││╰╮╭─╯╰────╮│╭╯╭╯╰╮│╭─╯╭─╯╭─╯╰─╯╰╯╰─────╮╭╮╭╯x│││╭───╯╭╮││╰╮╭╯││╭╯╰╮╰╯.    88
-....╰─────╮│╭╮╭──╮x╰╯╰╮╰╮╭╯│╭╯╰╯╭╮╭──╮╭╯╭─╮╭──╮╰────╮╰╮╰─╯╭╯╭╮╭╮│╰╯│╰╯╭╯│╭╮╭╮╭──╯╰╯╭╯╭─╯│╰─╮│t╭╯╭╮╭╮╭╮╭╮╭╮x╭╮╰╯╰╯x╭╯││╰╮╭──╯│││x╰╯x╰╯╰╮╭╯...    89
-....╭───╮╭╯│││╰─╮│╭──╮╰╮││╭╯╰───╯╰╯╭─╯╰─╯t│╰─╮│╭─╮╭╮╰╮╰╮╭╮│╭╯╰╯│╰─╮│╭─╯╭╯│╰╯│╰──╮╭─╯t╰─╮╰─╮│╰╮╰─╯╰╯╰╯╰╯╰╯╰─╯╰─╮x╭╮╰╮│╰─╯│╭──╯╰╯x╭────╮╰╯....    90
-....╰──╮│╰╮│││╭╮││╰╮╭╯╭╯╰╯│╭──────╮╰───╮╭─╯╭─╯││t╰╯╰─╯t││╰╯╰──╮│╭─╯││╭╮╰╮│╭─╯t╭╮││╭─╮╭╮╰─╮││╭╯╭╮╭╮╭╮╭─╮╭───╮╭╮╰╮││x╰╯╭─╮│╰──╮╭╮╭╯╭───╯......    91
-....╭─╮│╰─╯╰╯╰╯╰╯│╭╯╰╮╰──╮╰╯╭───╮╭╯╭─╮╭╯╰─╮╰─╮╰╯╭╮╭╮t╭╮╰╯╭╮╭╮╭╯│╰─╮│╰╯╰╮╰╯╰╮╭╮│╰╯││╭╯│╰╮╭╯╰╯╰─╯││╰╯│╰╮│╰─╮x╰╯╰╮╰╯╰─╮x│╭╯│╭──╯│││╭╯x╭╮.......    92
-....╰╮╰╯╭──╮╭─╮╭╮│╰╮╭╯╭──╯╭╮│x╭─╯╰─╯t╰╯╭╮╭╯╭╮╰╮╭╯╰╯╰╮││╭─╯╰╯│╰╮│t╭╯╰╮╭╮╰╮╭─╯││╰╮╭╯│╰╮╰╮││╭─────╯│╭╮╰─╯╰╮t╰──╮x╰────╯╭╯╰─╯╰─╮x││││╭╮││....╭╮.    93
-....╭╯╭─╯╭─╯╰╮│││╰─╯╰─╯╭─╮│││╭╯╭─╮╭────╯││╭╯╰─╯│╭───╯││╰───╮│╭╯╰╮╰─╮╰╯│t│╰─╮││╭╯╰╮╰╮│╭╯╰╯╰───╮╭╮╰╯╰───╮│╭──╮╰────╮╭─╯╭─╮╭──╯╭╯╰╯╰╯╰╯╰──╮.││.    94
-....│╭╯..╰─╮╭╯╰╯╰╮╭╮╭╮╭╯╭╯│││╰╮│╭╯│╭────╯╰╯╭╮╭─╯│╭╮╭╮│╰╮╭──╯╰╯╭╮╰──╯╭─╯╭╯╭╮╰╯│╰─╮╰─╯││╭─╮╭───╯││t╭╮╭╮t││╰─╮│╭╮╭──╯╰╮╭╯╭╯╰───╯╭╮╭╮╭─────╯x│╰╮    95
-....╰╯..╭──╯│╭─╮x╰╯│││╰╮╰╮│││x╰╯╰─╯╰─────╮╭╯│╰╮╭╯│╰╯╰╯╭╯╰──╮╭─╯╰───╮╰╮╭╯╭╯╰──╯╭╮╰──╮│╰╯t││╭╮t╭╯╰─╯╰╯│╭╯╰──╯╰╯│╰──╮╭╯╰╮╰──╮╭╮╭╯│││╰───────╯╭╯    96
-........╰╮╭╮││╭╯╭╮╭╯││╭╯╭╯│╰╯╭╮╭─────────╯│╭╯╭╯│t│╭─╮╭╯╭╮╭╮││╭╮╭─╮╭╯╭╯╰╮╰──╮╭─╯│╭╮╭╯╰╮t╭╯││╰╮│╭╮╭──╮╰╯╭╮╭──╮x╰───╯│╭╮╰──╮╰╯╰╯x╰╯╰╮╭╮╭╮╭╮╭╮│.    97
-........╭╯││││╰─╯╰╯╭╯│╰╮╰╮╰╮╭╯╰╯╭╮╭────╮╭─╯╰╮╰╮╰╮│╰╮││╭╯│││╰╯││╰╮│╰╮╰──╯╭──╯╰─╮││││╭╮╰╮│╭╯│╭╯││││╭─╯╭─╯╰╯╭╮╰────╮╭╯││╭─╮╰──╮╭──╮╭╯│╰╯╰╯││╰╯.    98
-........╰─╯╰╯╰────╮│x╰╮│╭╯╭╯╰─╮╭╯╰╯╭───╯╰──╮╰─╯╭╯│╭╯╰╯│╭╯│╰╮╭╯│╭╯│t│╭──╮╰╮╭╮╭╮││││╰╯│╭╯│╰╮│╰╮╰╯╰╯╰──╯t╭╮╭╯╰─────╯╰╮│╰╯x╰╮╭╮╰╯╭─╯│╭╯....╰╯...    99
-..........╭─────╮╭╯│╭─╯│╰─╯╭──╯│╭─╮╰──╮╭─╮t╰╮╭╮╰╮│╰╮t╭╯╰╮│╭╯╰╮│╰╮╰╮││╭╮╰╮╰╯╰╯│││││t╭╯│╭╯╭╯╰╮│╭╮╭╮t╭──╮││╰─╮╭──╮╭╮╭╯╰───╮╰╯╰─╮╰─╮╰╯╭╮........   100
-..........╰╮╭╮╭╮╰╯╭╯╰╮╭╯╭─╮╰───╯╰╮╰─╮╭╯╰╮│╭╮╰╯╰╮││╭╯╭╯╭─╯│╰╮t││╭╯╭╯╰╯││t╰╮╭╮╭╯│╰╯╰─╯╭╯╰╮╰╮╭╯││││╰╮╰─╮││╰──╯│╭─╯│││╭───╮╰───╮╰╮╭╯╭─╯╰╮.......   101
-...........╰╯╰╯╰╮╭╯╭╮╰╯x╰╮╰───╮x╭╯╭╮╰╯╭╮││││t╭─╯││╰╮╰╮│╭╮│╭╯╭╯╰╯╭╯╭╮t│╰──╯│╰╯t╰───╮╭╯╭─╯╭╯╰╮│││╰╮│╭╮││╰────╯╰─╮│╰╯│╭╮x╰╮╭──╯╭╯╰─╯╭─╮╰─╮.....   102
-...........╭────╯╰╮│╰─╮╭╮╰─╮╭╮╰─╯╭╯╰╮╭╯╰╯││╰╮╰─╮╰╯╭╯╭╯│││││╭╯╭──╯╭╯╰╮╰─╮╭╮╰╮╭──╮╭╮│╰╮╰─╮╰╮╭╯│││╭╯╰╯│││╭╮╭──╮╭─╯╰╮╭╯│╰─╮╰╯╭╮x╰──╮╭╯.│╭─╯.....   103
-...........╰───╮╭─╯╰─╮││╰──╯│╰──╮│╭─╯╰╮╭─╯│╭╯╭╮│╭─╯t╰╮│││││╰╮│t╭╮╰─╮│╭╮╰╯│╭╯╰─╮││││╭╯╭╮│╭╯│╭╯││╰──╮│││││╰─╮││╭╮x│╰─╯╭╮╰──╯╰───╮│╰─╮│╰╮......   104
-..........╭────╯│╭───╯│╰────╯╭╮╭╯│╰─╮╭╯│╭╮││╭╯│││╭─╮╭╯╰╯││╰╮││╭╯╰╮╭╯│││╭╮│╰─╮╭╯││││╰╮│╰╯╰╮│╰─╯╰╮╭╮│││╰╯│╭╮││╰╯╰─╯╭╮╭╯╰─╮╭─╮╭╮╭╯│╭─╯│╭╯......   105
-..........│╭╮╭─╮│╰╮╭─╮│x╭╮x╭─╯╰╯╭╯╭╮│╰╮││╰╯││x││││x╰╯╭╮╭╯│╭╯││╰─╮││x╰╯││╰╯╭─╯│x│││╰╮│╰╮x╭╯╰─╮╭─╯│╰╯││╭─╯│╰╯│╭╮╭╮╭╯╰╯╭╮x╰╯x│││╰╮│╰─╮╰╯.......   106
-.........╭╯│╰╯x╰╯x╰╯╭╯╰─╯╰╮╰─╮╭╮│╭╯╰╯╭╯│╰─╮││╭╯││╰──╮││╰╮││╭╯│╭─╯││╭──╯╰╮x│╭╮╰╮╰╯╰╮│╰╮│╭╯╭╮╭╯╰╮╭╯x╭╯│╰─╮╰─╮││╰╯╰╯╭──╯╰───╮╰╯│╭╯│╭─╯.........   107
-.........╰─╯x╭──────╯╭───╮│╭╮││╰╯│╭╮╭╯╭╯╭╮│││╰╮││╭─╮││╰─╯││╰╮│╰─╮││╰╮╭─╮╰╮││╰╮╰──╮││x│││╭╯││╭─╯╰╮╭╯╭╯╭╮│╭╮│││╭╮╭─╯╭─╮╭──╮╰─╮│╰╮│╰╮..........   108
-.............╰──╮╭╮╭╮│╭──╯╰╯│╰╯╭─╯││╰╮╰─╯╰╯│╰╮╰╯││x││╰╮╭╮│╰─╯│╭╮│││x││x│╭╯││x│╭──╯│╰╮│││╰╮╰╯╰─╮╭╯│╭╯x│╰╯││││╰╯╰╯╭─╯x╰╯╭╮╰──╯│╭╯╰─╯..........   109
-..............╭─╯│││││╰──╮╭╮╰─╮╰╮╭╯│╭╯x╭╮x╭╯╭╯╭─╯╰╮│╰╮╰╯│╰──╮╰╯││││╭╯│╭╯│╭╯│╭╯╰─╮╭╯╭╯││╰╮│╭───╯│╭╯╰╮╭╯╭╮│││╰─╮╭╮╰─────╯╰───╮│╰╮x╭╮..........   110
-..............╰╮╭╯││╰╯x╭─╯││╭╮╰─╯╰╮││x╭╯╰─╯╭╯x╰─╮╭╯╰─╯╭╮│╭──╯╭╮╰╯││╰╮│╰╮│╰─╯│╭╮╭╯╰╮╰╮╰╯x││╰╮╭╮╭╯╰╮╭╯│╭╯╰╯│╰╮╭╯│╰╮╭───╮╭╮╭╮╭╯╰─╯x│╰╮.........   111
-...............││╭╯╰╮x╭╯╭╮││││╭╮╭╮││╰╮╰──╮╭╯╭╮╭╮│╰──╮╭╯╰╯╰─╮╭╯╰╮x││x│╰╮╰╯x╭╮││╰╯╭╮╰╮╰──╮│╰╮││││╭─╯│╭╯╰╮╭─╯╭╯╰╮│╭╯╰──╮╰╯╰╯╰╯╭╮╭╮x│╭╯.........   112
-...............╰╯│╭─╯╭╯╭╯││╰╯││││╰╯│╭╯╭╮x│╰╮│╰╯╰╯╭╮╭╯│╭╮╭─╮╰╯╭╮╰╮╰╯╭╯╭╯x╭─╯╰╯╰──╯│╭╯╭╮╭╯│╭╯│││││╭╮││╭╮││╭╮╰─╮╰╯╰╮╭─╮╰──╮x╭─╯╰╯╰─╯╰╮.........   113
-..............╭╮x╰╯╭╮╰╮│x││x╭╯│││╭─╯│╭╯╰─╯╭╯╰──╮╭╯╰╯x││╰╯x╰╮╭╯╰╮│x╭╯╭╯╭─╯╭─╮╭─╮╭─╯╰─╯│╰╮││╭╯│││││││╰╯│││││╭╮╰─╮╭╯╰╮│╭─╮╰─╯╭──╮╭─╮╭╯.........   114
-.............╭╯╰╮╭╮│╰─╯│╭╯│╭╯╭╯│││╭╮││╭╮╭╮│x╭──╯╰─╮╭─╯╰─╮╭╮││╭╮│╰╮╰╮╰╮╰╮╭╯╭╯╰╮│╰╮╭──╮╰╮│││╰╮│││││││╭─╯│╰╯││╰╮╭╯╰╮╭╯╰╯x│╭╮╭╯╭╮│╰╮╰╯..........   115
-.............╰─╮╰╯││╭╮╭╯│╭╯│╭╯x╰╯││││││││││╭╯╭╮╭─╮│╰───╮╰╯││╰╯│╰╮╰╮│╭╯╭╯│x╰╮╭╯╰╮╰╯╭─╯x╰╯╰╯╭╯│││││││╰╮╭╯╭╮││x││╭╮│╰─╮╭╮│││╰─╯││╭╯............   116
-...............╰─╮╰╯│││╭╯╰╮││╭───╯││││││││││╭╯││╭╯╰──╮╭╯╭─╯│╭─╯╭╯╭╯╰╯╭╯╭╯╭╮││xx│╭─╯x╭╮x╭─╮╰╮│││││││╭╯│x│╰╯╰╮│╰╯│╰╮╭╯││╰╯│╭─╮│╰╯.............   117
-...............╭─╯╭╮│╰╯╰╮╭╯╰╯│╭─╮╭╯││╰╯││╰╯│╰╮│││╭╮╭─╯╰╮╰─╮│╰─╮╰╮╰──╮╰╮│╭╯╰╯╰╮╭╯╰──╮│╰─╯╭╯╭╯│╰╯││││╰╮│╭╯╭─╮││╭─╯╭╯╰─╯╰─╮│╰╮││...............   118
-...............╰╮╭╯╰╯╭─╮│╰╮x╭╯│╭╯│╭╯│╭─╯╰─╮╰─╯││╰╯│╰╮x╭╯╭─╯│╭─╯╭╯╭╮╭╯x╰╯│╭╮╭─╯╰╮╭╮╭╯╰─╮╭╯x│╭╯╭─╯│╰╯╭╯│╰╮│x╰╯││╭─╯╭╮╭╮╭╮││╭╯╰╯...............   119
-................╰╯x╭╮╰╮││╭╯╭╯╭╯╰╮││╭╯│╭╮╭─╯╭──╯╰─╮╰╮╰╮╰╮╰─╮│╰╮╭╯╭╯╰╯╭╮╭╮╰╯╰╯╭──╯│╰╯╭──╯╰─╮╰╯x│╭╮╰╮x│╭╯x││╭──╯│╰╮╭╯││╰╯╰╯│╰─╮................   120
-.................╭─╯╰─╯╰╯╰╮╰╮│╭─╯││╰╮│││╰╮x╰╮╭╮╭─╯x╰╮╰╮╰╮╭╯│╭╯│╭╯x╭─╯╰╯╰────╯╭─╮╰──╯╭╮╭─╮╰╮╭╮╰╯╰╮╰╮││╭─╯││╭─╮│╭╯│╭╯╰───╮╰╮╭╯................   121
-.................│╭╮╭─╮╭╮╭╯╭╯│╰╮╭╯╰─╯│││╭╯╭╮╰╯│╰─╮╭╮╰╮╰╮│╰╮╰╯╭╯╰╮x╰────────╮╭╯x│╭╮╭╮││╰╮╰╮╰╯╰─╮x│╭╯││╰╮╭╯╰╯╭╯│╰╮│╰╮╭───╯.╰╯.................   122
-................╭╯││╰╮│││╰╮╰─╯x│╰───╮╰╯│╰─╯╰╮x│╭─╯││╭╯╭╯╰╮╰─╮╰─╮╰╮╭╮╭──────╯╰─╮╰╯│││││╭╯x╰╮╭─╮│╭╯│x╰╯╭╯╰──╮╰╮╰╮││x│╰─╮......................   123
-................╰─╯│╭╯││╰─╯╭╮╭╮│╭╮╭─╯x╭╯╭─╮╭╯╭╯╰──╯│╰╮╰─╮╰╮╭╯╭─╯╭╯│╰╯╭────╮╭─╮╰─╮╰╯│││╰─╮╭╯╰╮╰╯╰╮╰──╮╰╮╭─╮╰╮╰╮│││╭╯╭─╯......................   124
-...................╰╯.╰╯..╭╯╰╯╰╯│╰╯x╭─╯╭╯x│╰╮│╭╮╭──╯╭╯╭╮╰╮│╰╮╰╮╭╯x│╭╮│╭───╯│x╰─╮╰╮╭╯││╭╮│╰╮╭╯╭──╯╭╮╭╯x╰╯x╰╮│x╰╯││╰╮╰──╮.....................   125
-..........................╰╮╭╮╭╮╰──╮╰─╮│╭─╯╭╯╰╯│╰──╮╰╮││╭╯│╭╯╭╯╰─╮╰╯╰╯╰─╮╭╮╰──╮│╭╯│╭╯╰╯╰╯╭╯╰╮╰─╮╭╯│╰──╮...││x╭─╯╰╮│╭─╮│.....................   126
-........................╭──╯│╰╯│╭──╯╭╮││╰╮╭╯x╭─╯╭╮╭╯╭╯││╰╮│╰╮│╭╮╭╯╭─────╯││╭╮╭╯╰╯╭╯│╭────╯╭╮╰─╮╰╯╭╯╭╮╭╯...╰╯x╰╮╭╮│││.╰╯.....................   127
-........................│╭──╯╭╮│╰─╮╭╯╰╯│╭╯╰─╮│╭╮│╰╯x╰╮││╭╯╰─╯╰╯││x╰────╮╭╯│││╰──╮╰╮│╰╮╭╮╭╮││╭╮╰╮x│╭╯╰╯........││││││........................   128
-........................╰╯.╭─╯╰╯╭╮││╭─╮│╰╮╭─╯│││╰──╮x╰╯│╰╮.....╰╯..╭───╯│╭╯│╰╮╭╮╰╮╰╯x╰╯││││││╰─╯x╰╯...........╰╯╰╯││........................   129
-...........................│╭─╮╭╯││││x╰╯╭╯╰─╮││╰╮╭╮╰──╮│╭╯.........╰───╮│╰╮╰╮││╰╮╰────╮││││╰╯.....................╰╯........................   130
-...........................╰╯.│╰╮││││╭──╯╭─╮│╰╯╭╯│╰╮╭─╯│╰╮...........╭─╯│╭╯╭╯╰╯x│╭╮╭─╮│╰╯││.................................................   131
-..........................╭───╯╭╯╰╯╰╯╰──╮╰╮│╰╮x╰╮│╭╯│xx╰─╯...........│╭╮│╰╮╰──╮╭╯│╰╯x╰╯..╰╯.................................................   132
-..........................╰╮╭──╯.....╭──╯╭╯╰─╯x╭╯│╰╮╰╮...............││││╭╯╭─╮││╭╯x╭╮.......................................................   133
-...........................╰╯........╰──╮╰─╮...╰╮│╭╯╭╯...............││╰╯│╭╯╭╯│││x╭╯│.......................................................   134
-...................................╭────╯╭╮╰───╮││╰╮│................╰╯..╰╯.│╭╯│╰─╯╭╯.......................................................   135
-...................................╰─╮╭─╮│╰──╮╭╯╰╯.╰╯.......................╰╯╭╯╭╮╭╯........................................................   136
-.....................................╰╯.│╰──╮╰╯...............................╰╮│╰╯.........................................................   137
-.....................................╭──╯╭─╮╰──╮...............................╰╯...........................................................   138
-.....................................╰───╯.╰───╯............................................................................................   139
-";
-    let answer = maze.chars().filter(|&ch| ch == 't').count();
+    let answer = count_enclosed(input);
+
     println!("Part 2 answer is {answer}");
 }
 
@@ -482,4 +404,64 @@ LJ.LJ";
         assert_eq!(calc_steps(INPUT_1), 4);
         assert_eq!(calc_steps(INPUT_2), 8);
     }
+
+    const INPUT_3: &str = "...........
+.S-------7.
+.|F-----7|.
+.||.....||.
+.||.....||.
+.|L-7.F-J|.
+.|..|.|..|.
+.L--J.L--J.
+...........";
+
+    const INPUT_4: &str = "..........
+.S------7.
+.|F----7|.
+.||....||.
+.||....||.
+.|L-7F-J|.
+.|..||..|.
+.L--JL--J.
+..........";
+
+    const INPUT_5: &str = ".F----7F7F7F7F-7....
+.|F--7||||||||FJ....
+.||.FJ||||||||L7....
+FJL7L7LJLJ||LJ.L-7..
+L--J.L7...LJS7F-7L7.
+....F-J..F7FJ|L7L7L7
+....L7.F7||L7|.L7L7|
+.....|FJLJ|FJ|F7|.LJ
+....FJL-7.||.||||...
+....L---J.LJ.LJLJ...";
+
+    const INPUT_6: &str = "FF7FSF7F7F7F7F7F---7
+L|LJ||||||||||||F--J
+FL-7LJLJ||||||LJL-77
+F--JF--7||LJLJ7F7FJ-
+L---JF-JLJ.||-FJLJJ7
+|F|F-JF---7F7-L7L|7|
+|FFJF7L7F-JF7|JL---7
+7-L-JL7||F7|L7F-7F7|
+L.L7LFJ|||||FJL7||LJ
+L7JLJL-JLJLJL--JLJ.L";
+
+    #[test]
+    fn test_enclosed() {
+        assert_eq!(count_enclosed(INPUT_1), 1);
+        assert_eq!(count_enclosed(INPUT_2), 1);
+        assert_eq!(count_enclosed(INPUT_3), 4);
+        assert_eq!(count_enclosed(INPUT_4), 4);
+        assert_eq!(count_enclosed(INPUT_5), 8);
+        assert_eq!(count_enclosed(INPUT_6), 10);
+    }
+
+    #[test]
+    fn test_start_shape() {
+        let maze = Maze::from(INPUT_1);
+        assert_eq!(maze.start_shape(&maze.get_loop_poses()), 'F');
+        let maze = Maze::from(INPUT_6);
+        assert_eq!(maze.start_shape(&maze.get_loop_poses()), '7');
+    }
 }
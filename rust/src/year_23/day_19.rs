@@ -1,6 +1,9 @@
 use std::collections::{HashMap, VecDeque};
 
-use crate::utls::read_text_from_file;
+use crate::{
+    dot::{self, Graph, Shape},
+    utls::read_text_from_file,
+};
 
 #[derive(Debug, Clone, Copy)]
 enum Category {
@@ -54,7 +57,26 @@ enum Rule {
     None,
 }
 
+impl Category {
+    fn name(self) -> char {
+        match self {
+            Category::X => 'x',
+            Category::M => 'm',
+            Category::A => 'a',
+            Category::S => 's',
+        }
+    }
+}
+
 impl Rule {
+    fn label(&self) -> String {
+        match self {
+            Rule::GreaterThan(cat, val) => format!("{}>{val}", cat.name()),
+            Rule::SmallerThan(cat, val) => format!("{}<{val}", cat.name()),
+            Rule::None => String::from("else"),
+        }
+    }
+
     fn apply(&self, machine_parts: &MachineParts) -> bool {
         match self {
             Rule::GreaterThan(cat, val) => machine_parts.scores[*cat as usize] > *val,
@@ -123,6 +145,34 @@ fn parse_workflows(text: &str) -> HashMap<&str, Vec<Condition<'_>>> {
         .collect()
 }
 
+/// Builds the graph of the workflows with the rules as edge labels, ending in the accepted and
+/// rejected nodes.
+fn build_graph(workflows: &HashMap<&str, Vec<Condition>>) -> Graph {
+    let mut graph = Graph::directed();
+    graph.labelled_node("A", "Accepted", Shape::DoubleCircle);
+    graph.labelled_node("R", "Rejected", Shape::Octagon);
+
+    for (&name, conditions) in workflows {
+        let shape = if name == "in" {
+            Shape::Triangle
+        } else {
+            Shape::Box
+        };
+        graph.node(name, shape);
+
+        for cond in conditions {
+            let target = match cond.inst {
+                Inst::GoTo(address) => address,
+                Inst::Accepted => "A",
+                Inst::Rejected => "R",
+            };
+            graph.labelled_edge(name, target, cond.rule.label());
+        }
+    }
+
+    graph
+}
+
 fn get_accepted_sum(input: &str) -> usize {
     let (workflows, parts) = input.split_once("\n\n").unwrap();
     let workflows_map = parse_workflows(workflows);
@@ -212,6 +262,11 @@ fn part_1(input: &str) {
     let answer = get_accepted_sum(input);
 
     println!("Part 1 answer is {answer}");
+
+    dot::export("23_19_workflows", || {
+        let (workflows, _) = input.split_once("\n\n").unwrap();
+        build_graph(&parse_workflows(workflows))
+    });
 }
 
 fn part_2(input: &str) {
@@ -1,4 +1,12 @@
-use crate::utls::read_text_from_file;
+use std::collections::HashMap;
+
+use crate::{
+    dot::{self, Graph, Shape},
+    utls::read_text_from_file,
+};
+
+/// Upper bound of button presses when simulating the cycles of rx feeders.
+const MAX_PRESSES: usize = 1 << 20;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum Pulse {
@@ -211,7 +219,8 @@ impl Machine {
         summed.0 * summed.1
     }
 
-    fn apply_round_min(&mut self) -> bool {
+    /// Runs one button press and returns the modules sending a high pulse to the target.
+    fn apply_round_min(&mut self, target: &str) -> Vec<&'static str> {
         let mut broadcaster = self
             .modules
             .iter()
@@ -222,15 +231,14 @@ impl Machine {
         // input doesn't matter for broadcaster
         let mut pulses = broadcaster.apply("", Pulse::Low).clone();
 
-        let mut found = false;
+        let mut senders = Vec::new();
         while !pulses.is_empty() {
-            // dn is the last conjunction before rx
-            if pulses
-                .iter()
-                .any(|p| matches!(p.pulse, Pulse::High) && matches!(p.target, "dn"))
-            {
-                found = true;
-            }
+            senders.extend(
+                pulses
+                    .iter()
+                    .filter(|p| matches!(p.pulse, Pulse::High) && p.target == target)
+                    .map(|p| p.sender),
+            );
             let new_pulses = pulses
                 .iter()
                 .flat_map(|state| {
@@ -244,24 +252,201 @@ impl Machine {
             pulses = new_pulses;
         }
 
-        found
+        senders
     }
 
-    fn apply_min(&mut self) -> usize {
-        // TODO: Read the following article
-        // https://www.electronics-tutorials.ws/counter/mod-counters.html
+    /// Builds the graph of the modules, where the module types have their own shapes and the
+    /// targets without modules like rx are octagons.
+    fn build_graph(&self) -> Graph {
+        let mut graph = Graph::directed();
+        for module in &self.modules {
+            let shape = match module.module_type {
+                ModuleType::FlipFlop { .. } => Shape::Box,
+                ModuleType::Conjunction { .. } => Shape::Diamond,
+                ModuleType::BroadCast => Shape::Triangle,
+            };
+            graph.node(module.name, shape);
+            for target in &module.targets {
+                graph.edge(module.name, target);
+            }
+        }
 
-        self.fill_conj_initial();
+        let mut outputs: Vec<_> = self
+            .modules
+            .iter()
+            .flat_map(|m| &m.targets)
+            .filter(|target| self.get_module(target).is_none())
+            .collect();
+        outputs.sort_unstable();
+        outputs.dedup();
+        for output in outputs {
+            graph.node(output, Shape::Octagon);
+        }
 
-        let mut counts = Vec::new();
+        graph
+    }
+
+    fn get_module(&self, name: &str) -> Option<&Module> {
+        self.modules.iter().find(|m| m.name == name)
+    }
+
+    fn is_conjunction(&self, name: &str) -> bool {
+        self.get_module(name)
+            .is_some_and(|m| matches!(m.module_type, ModuleType::Conjunction { .. }))
+    }
 
-        for i in 0..4096 {
-            if self.apply_round_min() {
-                counts.push(i + 1);
+    fn is_flipflop(&self, name: &str) -> bool {
+        self.get_module(name)
+            .is_some_and(|m| matches!(m.module_type, ModuleType::FlipFlop { .. }))
+    }
+
+    /// The conjunction sending to rx and the modules feeding it.
+    fn rx_feeders(&self) -> Option<(&'static str, Vec<&'static str>)> {
+        let last = self.modules.iter().find(|m| m.targets.contains(&"rx"))?;
+        let ModuleType::Conjunction { input_map } = &last.module_type else {
+            return None;
+        };
+
+        Some((last.name, input_map.iter().map(|(name, _)| *name).collect()))
+    }
+
+    /// Each flip-flop chain starting from the broadcaster is a binary counter where the first
+    /// flip-flop is the lowest bit. The flip-flops sending to the chain conjunction are the bits
+    /// set in the counter period. Once all of them are high, the conjunction resets the counter
+    /// by sending to the first flip-flop and to the ones with unset bits.
+    /// Returns the counter period with the conjunction of the chain.
+    fn chain_period(&self, start: &'static str) -> Option<(usize, &'static str)> {
+        let mut members = Vec::new();
+        let mut hub = None;
+        let mut period = 0;
+        let mut current = start;
+
+        loop {
+            if !self.is_flipflop(current) || members.len() >= usize::BITS as usize {
+                return None;
+            }
+            let module = self.get_module(current)?;
+            let (conjs, flips): (Vec<&'static str>, Vec<&'static str>) = module
+                .targets
+                .iter()
+                .partition(|target| self.is_conjunction(target));
+
+            if conjs.len() > 1 || flips.len() > 1 {
+                return None;
+            }
+            if let Some(&conj) = conjs.first() {
+                if *hub.get_or_insert(conj) != conj {
+                    return None;
+                }
+                period |= 1 << members.len();
+            }
+            members.push(current);
+
+            match flips.first() {
+                Some(&next) => current = next,
+                None => break,
             }
         }
 
-        lcm(&counts)
+        let hub = hub?;
+        let hub_targets = &self.get_module(hub)?.targets;
+        let resets_counter = members.iter().enumerate().all(|(bit, member)| {
+            let bit_set = period & (1 << bit) != 0;
+            (bit == 0 || !bit_set) == hub_targets.contains(member)
+        });
+
+        resets_counter.then_some((period, hub))
+    }
+
+    /// Computes the presses directly from the counters feeding rx. Returns None if the machine
+    /// doesn't have this structure.
+    fn analyse_min(&self) -> Option<usize> {
+        let (_, feeders) = self.rx_feeders()?;
+        let broadcaster = self
+            .modules
+            .iter()
+            .find(|m| matches!(m.module_type, ModuleType::BroadCast))?;
+
+        let mut periods = Vec::new();
+        let mut fed = Vec::new();
+        for &start in &broadcaster.targets {
+            let (period, hub) = self.chain_period(start)?;
+
+            // The chain conjunction can reach the feeder through an inverter.
+            let feeder = if feeders.contains(&hub) {
+                hub
+            } else {
+                self.get_module(hub)?
+                    .targets
+                    .iter()
+                    .find(|target| feeders.contains(target) && self.is_conjunction(target))
+                    .copied()?
+            };
+
+            fed.push(feeder);
+            periods.push(period);
+        }
+
+        fed.sort_unstable();
+        fed.dedup();
+        (fed.len() == feeders.len() && periods.len() == feeders.len()).then(|| lcm(&periods))
+    }
+
+    /// Presses the button until the cycle of each feeder of rx is known.
+    /// Gives up once the machine returns to its initial state or after [`MAX_PRESSES`].
+    fn simulate_min(&mut self) -> Result<usize, String> {
+        let (last, feeders) = self
+            .rx_feeders()
+            .ok_or_else(|| String::from("rx isn't fed by a conjunction"))?;
+        let initial_state = self.modules.clone();
+
+        let mut hits: HashMap<&str, Vec<usize>> = HashMap::new();
+        let mut round = 0;
+        while feeders
+            .iter()
+            .any(|f| hits.get(f).is_none_or(|rounds| rounds.len() < 2))
+        {
+            if round == MAX_PRESSES {
+                return Err(format!(
+                    "feeders of rx didn't cycle within {MAX_PRESSES} presses"
+                ));
+            }
+
+            round += 1;
+            for sender in self.apply_round_min(last) {
+                let rounds = hits.entry(sender).or_default();
+                if rounds.last() != Some(&round) {
+                    rounds.push(round);
+                }
+            }
+
+            if self.modules == initial_state {
+                return Err(format!(
+                    "machine repeats itself after {round} presses without all feeders firing"
+                ));
+            }
+        }
+
+        let mut periods = Vec::with_capacity(feeders.len());
+        for f in &feeders {
+            let rounds = &hits[f];
+            let period = rounds[1] - rounds[0];
+            if rounds[0] != period {
+                return Err(format!("cycle of {f} doesn't start at zero"));
+            }
+            periods.push(period);
+        }
+
+        Ok(lcm(&periods))
+    }
+
+    fn apply_min(&mut self) -> Result<usize, String> {
+        self.fill_conj_initial();
+
+        match self.analyse_min() {
+            Some(presses) => Ok(presses),
+            None => self.simulate_min(),
+        }
     }
 }
 
@@ -295,7 +480,7 @@ fn get_pulses_prod(input: &'static str) -> usize {
     machine.apply()
 }
 
-fn get_pulses_min(input: &'static str) -> usize {
+fn get_pulses_min(input: &'static str) -> Result<usize, String> {
     let mut machine = Machine::from(input);
     machine.apply_min()
 }
@@ -307,9 +492,12 @@ fn part_1(input: &'static str) {
 }
 
 fn part_2(input: &'static str) {
-    let answer = get_pulses_min(input);
+    match get_pulses_min(input) {
+        Ok(answer) => println!("Part 2 answer is {answer}"),
+        Err(err) => println!("Part 2 has no answer: {err}"),
+    }
 
-    println!("Part 2 answer is {answer}");
+    dot::export("23_20_modules", || Machine::from(input).build_graph());
 }
 
 pub fn run() {
@@ -334,9 +522,46 @@ mod test {
 %b -> con
 &con -> output";
 
+    // Two counters with the periods 11 and 13
+    const INPUT_3: &str = "broadcaster -> a0, b0
+%a0 -> ha, a1
+%a1 -> ha, a2
+%a2 -> a3
+%a3 -> ha
+&ha -> a0, a2, ia
+&ia -> fin
+%b0 -> hb, b1
+%b1 -> b2
+%b2 -> hb, b3
+%b3 -> hb
+&hb -> b0, b1, ib
+&ib -> fin
+&fin -> rx";
+
     #[test]
     fn test_solution() {
         assert_eq!(get_pulses_prod(INPUT_1), 32000000);
         assert_eq!(get_pulses_prod(INPUT_2), 11687500);
     }
+
+    #[test]
+    fn test_min() {
+        let mut machine = Machine::from(INPUT_3);
+        machine.fill_conj_initial();
+        assert_eq!(machine.chain_period("a0"), Some((11, "ha")));
+        assert_eq!(machine.chain_period("b0"), Some((13, "hb")));
+        assert_eq!(machine.analyse_min(), Some(143));
+        assert_eq!(machine.simulate_min(), Ok(143));
+
+        assert_eq!(get_pulses_min(INPUT_3), Ok(143));
+    }
+
+    #[test]
+    fn test_min_no_cycle() {
+        let input = "broadcaster -> a
+%a -> fin
+%d -> fin
+&fin -> rx";
+        assert!(get_pulses_min(input).is_err());
+    }
 }
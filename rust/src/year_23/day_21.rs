@@ -1,7 +1,4 @@
-use std::{
-    cmp::Reverse,
-    collections::{BinaryHeap, HashSet},
-};
+use std::collections::{HashMap, VecDeque};
 
 use rayon::prelude::{IntoParallelIterator, ParallelIterator};
 
@@ -42,7 +39,20 @@ impl From<&'static str> for Grid {
     }
 }
 
+/// How many tiles the distance search covers in each direction around the start tile.
+const TILE_RADIUS: isize = 4;
+
+const STEPS_2: usize = 26501365;
+
 impl Grid {
+    fn rows(&self) -> isize {
+        self.cells.len() as isize
+    }
+
+    fn cols(&self) -> isize {
+        self.cells[0].len() as isize
+    }
+
     fn get_possible_moves(&self, point: &Point) -> Vec<Point> {
         let moves = vec![
             Point::new(point.row - 1, point.col),
@@ -51,63 +61,164 @@ impl Grid {
             Point::new(point.row, point.col + 1),
         ];
 
-        moves
-            .into_iter()
-            .filter(|p| {
-                let b = self.get_content(p).unwrap();
-                matches!(b, b'.' | b'S')
-            })
-            .collect()
+        moves.into_iter().filter(|p| self.is_plot(p)).collect()
     }
 
-    fn get_content(&self, point: &Point) -> Option<&u8> {
-        let rows_total = self.cells.len() as isize;
-        let cols_total = self.cells[0].len() as isize;
-        let mut row = point.row;
-        let mut col = point.col;
-        while row.is_negative() {
-            row += rows_total;
-        }
-        while col.is_negative() {
-            col += cols_total;
-        }
+    fn is_plot(&self, point: &Point) -> bool {
+        matches!(self.get_content(point), Some(b'.' | b'S'))
+    }
 
-        let row = (row % rows_total) as usize;
-        let col = (col % cols_total) as usize;
+    fn get_content(&self, point: &Point) -> Option<&u8> {
+        let row = point.row.rem_euclid(self.rows()) as usize;
+        let col = point.col.rem_euclid(self.cols()) as usize;
 
         self.cells.get(row).and_then(|r| r.get(col))
     }
 
-    fn calc_max_plots(&mut self, max_steps: usize) -> usize {
-        let mut plots_set = HashSet::new();
-
-        let mut queue = BinaryHeap::new();
-        queue.push((Reverse(0), self.start_pos));
+    /// The tile of the infinite map containing the point, with the start tile at zero.
+    fn tile_of(&self, point: &Point) -> Point {
+        Point::new(
+            point.row.div_euclid(self.rows()),
+            point.col.div_euclid(self.cols()),
+        )
+    }
 
-        while let Some((Reverse(mut steps), point)) = queue.pop() {
-            if steps > max_steps {
-                continue;
+    /// Breadth first search on the infinite map from the start, visiting the points accepted
+    /// by the filter given the point and its distance.
+    fn calc_distances(&self, accept: impl Fn(&Point, usize) -> bool) -> HashMap<Point, usize> {
+        let mut distances = HashMap::from([(self.start_pos, 0)]);
+        let mut queue = VecDeque::from([(self.start_pos, 0)]);
+
+        while let Some((point, steps)) = queue.pop_front() {
+            for next in self.get_possible_moves(&point) {
+                if !distances.contains_key(&next) && accept(&next, steps + 1) {
+                    distances.insert(next, steps + 1);
+                    queue.push_back((next, steps + 1));
+                }
             }
+        }
 
-            if !plots_set.insert((point, steps)) {
-                continue;
+        distances
+    }
+
+    /// A plot can be reached with exactly the given steps if its distance isn't greater and has
+    /// the same parity, since the remaining steps can be spent going back and forth.
+    fn calc_max_plots(&self, max_steps: usize) -> usize {
+        self.calc_distances(|_, steps| steps <= max_steps)
+            .values()
+            .filter(|&&steps| steps % 2 == max_steps % 2)
+            .count()
+    }
+
+    /// Counts the reachable plots for any steps count on a square map. Distances are searched
+    /// for the tiles around the start only. Any tile beyond them is reached through one of the
+    /// outermost searched tiles, where each further tile adds the side length to the distance.
+    fn count_reachable(&self, max_steps: usize) -> usize {
+        assert_eq!(self.rows(), self.cols(), "map must be a square");
+        let side = self.rows() as usize;
+
+        self.calc_distances(|point, _| {
+            let tile = self.tile_of(point);
+            tile.row.abs() <= TILE_RADIUS && tile.col.abs() <= TILE_RADIUS
+        })
+        .into_iter()
+        .filter(|(_, steps)| *steps <= max_steps)
+        .map(|(point, steps)| {
+            let tile = self.tile_of(&point);
+            let edge_row = tile.row.abs() == TILE_RADIUS;
+            let edge_col = tile.col.abs() == TILE_RADIUS;
+            match (edge_row, edge_col) {
+                (true, true) => count_copies(steps, max_steps, side, true),
+                (true, false) | (false, true) => count_copies(steps, max_steps, side, false),
+                (false, false) => usize::from(steps % 2 == max_steps % 2),
             }
+        })
+        .sum()
+    }
 
-            steps += 1;
-            for possible_point in self.get_possible_moves(&point) {
-                queue.push((Reverse(steps), possible_point));
+    /// Checks the assumptions needed to extrapolate the reachable plots with a quadratic
+    /// polynomial, returning the first one missing in this map.
+    fn check_quadratic(&self, max_steps: usize) -> Result<(), String> {
+        let (rows, cols) = (self.rows(), self.cols());
+        if rows != cols {
+            return Err(format!("map isn't a square: {rows}x{cols}"));
+        }
+        if self.start_pos != Point::new(rows / 2, cols / 2) {
+            return Err(format!("start isn't in the center: {:?}", self.start_pos));
+        }
+        if max_steps as isize % cols != self.start_pos.col {
+            return Err(format!(
+                "steps {max_steps} don't end at the edge of a tile with side {cols}"
+            ));
+        }
+
+        let clear_row = |row| (0..cols).all(|col| self.is_plot(&Point::new(row, col)));
+        let clear_col = |col| (0..rows).all(|row| self.is_plot(&Point::new(row, col)));
+        for row in [0, self.start_pos.row, rows - 1] {
+            if !clear_row(row) {
+                return Err(format!("row {row} isn't clear"));
+            }
+        }
+        for col in [0, self.start_pos.col, cols - 1] {
+            if !clear_col(col) {
+                return Err(format!("column {col} isn't clear"));
             }
         }
 
-        plots_set
-            .iter()
-            .filter(|(_, count)| *count == max_steps)
-            .count()
+        Ok(())
+    }
+
+    /// Fits a quadratic polynomial through the plots reached after one, two and three tiles
+    /// and extrapolates it to the given steps, which must pass [`Grid::check_quadratic()`].
+    fn calc_quadratic(&self, max_steps: usize) -> usize {
+        let side = self.cols() as usize;
+        let offset = max_steps % side;
+
+        // Get enough data from the normal algorithm used in part 1
+        let mut answer_samples: Vec<_> = (0..=2)
+            .into_par_iter()
+            .map(|i| self.calc_max_plots(offset + i * side))
+            .collect();
+
+        // There is no guarantee that rayon will return item in order, even though it must be always
+        // the case here since each iteration will take significant more time than the previous one
+        answer_samples.sort_unstable();
+
+        // Get the rest of answers by extrapolating the last value from the answers until we reach
+        // the target value
+        while answer_samples.len() <= max_steps / side {
+            answer_samples.push(extrapolate(&answer_samples));
+        }
+
+        *answer_samples.last().unwrap()
+    }
+}
+
+/// Counts the copies of a plot with the given distance in the tiles continuing away from the
+/// start, where the copy number `m` has the distance `steps + m * side`. A corner tile has
+/// `m + 1` copies at the number `m` since they spread in two directions.
+fn count_copies(steps: usize, max_steps: usize, side: usize, corner: bool) -> usize {
+    let max_copy = (max_steps - steps) / side;
+    // With an odd side every other copy has the right parity
+    let (first, stride) = match (side % 2, (max_steps - steps) % 2) {
+        (0, 0) => (0, 1),
+        (0, _) => return 0,
+        (_, parity) => (parity, 2),
+    };
+    if max_copy < first {
+        return 0;
+    }
+
+    let last = (max_copy - first) / stride;
+    if corner {
+        (last + 1) * (first + 1) + stride * last * (last + 1) / 2
+    } else {
+        last + 1
     }
 }
 
 fn calc_max_plots(input: &'static str, max_steps: usize) -> usize {
-    let mut grid = Grid::from(input);
+    let grid = Grid::from(input);
 
     grid.calc_max_plots(max_steps)
 }
@@ -119,31 +230,19 @@ fn part_1(input: &'static str) {
 }
 
 fn part_2(input: &'static str) {
-    // The grid is a square with a side length of 131
-    // The target is exactly 26501365 steps = 202300 * 131 + (131 / 2)
-    // This will only solve for the case where total total_steps = NUM * square_side_length + (square_side_length / 2)
-
-    // Get enough data from the normal algorithm used in part 1
-    let mut answer_samples: Vec<_> = (0..=2)
-        .into_par_iter()
-        .map(|i| {
-            let mut grid = Grid::from(input);
-            grid.calc_max_plots(65 + i * 131)
-        })
-        .collect();
-
-    // There is no guarantee that rayon will return item in order, even though it must be always
-    // the case here since each iteration will take significant more time than the previous one
-    answer_samples.sort_unstable();
-
-    // Get the rest of answers by extrapolating the last value from the answers until we reach
-    // the target value
-    while answer_samples.len() <= (26501365 - 65) / 131 {
-        answer_samples.push(extrapolate(&answer_samples));
+    let grid = Grid::from(input);
+    let answer = grid.count_reachable(STEPS_2);
+
+    match grid.check_quadratic(STEPS_2) {
+        Ok(()) => {
+            let quadratic = grid.calc_quadratic(STEPS_2);
+            if quadratic != answer {
+                println!("Quadratic extrapolation gives {quadratic} instead of {answer}");
+            }
+        }
+        Err(err) => println!("Quadratic extrapolation doesn't apply: {err}"),
     }
 
-    let answer = answer_samples.last().unwrap();
-
     println!("Part 2 answer is {answer}");
 }
 
@@ -187,4 +286,48 @@ mod test {
         assert_eq!(calc_max_plots(INPUT, 50), 1594);
         assert_eq!(calc_max_plots(INPUT, 100), 6536);
     }
+
+    #[test]
+    fn test_reachable() {
+        let count_reachable = |input, steps| Grid::from(input).count_reachable(steps);
+        assert_eq!(count_reachable(INPUT, 6), 16);
+        assert_eq!(count_reachable(INPUT, 10), 50);
+        assert_eq!(count_reachable(INPUT, 50), 1594);
+        assert_eq!(count_reachable(INPUT, 100), 6536);
+        assert_eq!(count_reachable(INPUT, 500), 167004);
+        assert_eq!(count_reachable(INPUT, 1000), 668697);
+        assert_eq!(count_reachable(INPUT, 5000), 16733044);
+
+        for steps in [0, 1, 7, 33, 64, 121, 150] {
+            assert_eq!(count_reachable(INPUT, steps), calc_max_plots(INPUT, steps));
+        }
+    }
+
+    // The example with clear middle and border lines
+    const INPUT_CLEAR: &str = "...........
+......##.#.
+.###..#..#.
+..#.#....#.
+....#.#....
+.....S.....
+.##......#.
+.......##..
+.##.#.#.##.
+.##...#.##.
+...........";
+
+    #[test]
+    fn test_quadratic() {
+        let grid = Grid::from(INPUT);
+        assert!(grid.check_quadratic(5 + 11 * 20).is_err());
+
+        let grid = Grid::from(INPUT_CLEAR);
+        assert!(grid.check_quadratic(6 + 11 * 20).is_err());
+        for steps in [5 + 11 * 4, 5 + 11 * 9, 5 + 11 * 20] {
+            assert!(grid.check_quadratic(steps).is_ok());
+            let exact = grid.count_reachable(steps);
+            assert_eq!(exact, grid.calc_max_plots(steps));
+            assert_eq!(exact, grid.calc_quadratic(steps));
+        }
+    }
 }
@@ -1,4 +1,4 @@
-use std::collections::{HashMap, HashSet, VecDeque};
+use std::collections::HashMap;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 struct Point {
@@ -12,251 +12,196 @@ impl Point {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
-struct State {
-    curr: Point,
-    prev: Point,
-    visited_slopes: Vec<Point>,
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Dir {
+    Up,
+    Down,
+    Left,
+    Right,
 }
 
-impl State {
-    fn new(curr: Point, prev: Point, visited_slopes: Vec<Point>) -> Self {
-        Self {
-            curr,
-            prev,
-            visited_slopes,
+impl Dir {
+    const ALL: [Dir; 4] = [Dir::Up, Dir::Down, Dir::Left, Dir::Right];
+
+    /// The direction a slope forces, which is None for the other cells.
+    fn from_slope(content: u8) -> Option<Dir> {
+        match content {
+            b'^' => Some(Dir::Up),
+            b'v' => Some(Dir::Down),
+            b'<' => Some(Dir::Left),
+            b'>' => Some(Dir::Right),
+            _ => None,
         }
     }
 }
 
+/// The maze compressed to its junctions with dense ids, so the visited nodes fit in a bitmask.
 #[derive(Debug)]
-struct Maze {
-    cells: Vec<&'static [u8]>,
+struct Graph {
+    /// The outgoing edges of each node as the target and the count of steps.
+    edges: Vec<Vec<(usize, usize)>>,
+    start: usize,
+    end: usize,
 }
 
-impl From<&'static str> for Maze {
-    fn from(value: &'static str) -> Self {
-        let cells = value.lines().map(|line| line.as_bytes()).collect();
-
-        Self { cells }
+impl Graph {
+    /// The node every path to the end must pass with the steps between them, if the end has
+    /// a single entry. Reaching it means going straight to the end since any detour would
+    /// visit it again.
+    fn last_junction(&self) -> Option<(usize, usize)> {
+        let mut entries = self.edges.iter().enumerate().flat_map(|(node, edges)| {
+            edges
+                .iter()
+                .filter(|(target, _)| *target == self.end)
+                .map(move |(_, steps)| (node, *steps))
+        });
+
+        let entry = entries.next()?;
+        entries.next().is_none().then_some(entry)
     }
-}
 
-impl Maze {
-    fn reached_target(&self, p: &Point) -> bool {
-        p.row == self.cells.len() - 1 && p.col == self.cells[0].len() - 2
-    }
+    fn longest_path(&self) -> Option<usize> {
+        assert!(self.edges.len() <= 64, "too many junctions for the bitmask");
 
-    fn get_content(&self, p: &Point) -> u8 {
-        self.cells[p.row][p.col]
+        let exit = self.last_junction();
+        self.longest_path_recr(self.start, 1 << self.start, exit)
     }
 
-    fn get_next_moves(&self, state: &State) -> Vec<Point> {
-        let mut moves = Vec::new();
-
-        let up = Point::new(state.curr.row - 1, state.curr.col);
-        if up != state.prev && matches!(self.get_content(&up), b'.' | b'^') {
-            moves.push(up);
-        }
-
-        let down = Point::new(state.curr.row + 1, state.curr.col);
-        if down != state.prev && matches!(self.get_content(&down), b'.' | b'v') {
-            moves.push(down);
+    fn longest_path_recr(
+        &self,
+        node: usize,
+        visited: u64,
+        exit: Option<(usize, usize)>,
+    ) -> Option<usize> {
+        if node == self.end {
+            return Some(0);
         }
 
-        let left = Point::new(state.curr.row, state.curr.col - 1);
-        if left != state.prev && matches!(self.get_content(&left), b'.' | b'<') {
-            moves.push(left);
+        if let Some((last, steps)) = exit
+            && node == last
+        {
+            return Some(steps);
         }
 
-        let right = Point::new(state.curr.row, state.curr.col + 1);
-        if right != state.prev && matches!(self.get_content(&right), b'.' | b'>') {
-            moves.push(right);
-        }
-
-        moves
+        self.edges[node]
+            .iter()
+            .filter(|(next, _)| visited & (1 << next) == 0)
+            .filter_map(|&(next, steps)| {
+                self.longest_path_recr(next, visited | (1 << next), exit)
+                    .map(|dist| dist + steps)
+            })
+            .max()
     }
+}
 
-    fn find_max_steps(&self) -> usize {
-        // real input and the test has the first entrance
-        let initial_state = State::new(Point::new(1, 1), Point::new(0, 1), Vec::new());
-        let mut queue = VecDeque::new();
-        // We've already made the first step
-        queue.push_back((1, initial_state.clone()));
-
-        let mut states_map: HashMap<State, usize> = HashMap::new();
-
-        let mut max_steps = 0;
-
-        while let Some((steps, state)) = queue.pop_front() {
-            if self.reached_target(&state.curr) {
-                max_steps = max_steps.max(steps);
-                continue;
-            }
-
-            if let Some(saved_steps) = states_map.get_mut(&state) {
-                if steps > *saved_steps {
-                    *saved_steps = steps;
-                } else {
-                    continue;
-                }
-            }
-
-            for p in self.get_next_moves(&state) {
-                let mut clone = state.clone();
-                let mut next_steps = steps + 2;
-                let next_point = match self.get_content(&p) {
-                    b'^' => {
-                        if clone.visited_slopes.contains(&p) {
-                            continue;
-                        }
-                        clone.visited_slopes.push(p);
-
-                        Point::new(p.row - 1, p.col)
-                    }
-                    b'v' => {
-                        if clone.visited_slopes.contains(&p) {
-                            continue;
-                        }
-                        clone.visited_slopes.push(p);
-
-                        Point::new(p.row + 1, p.col)
-                    }
-                    b'>' => {
-                        if clone.visited_slopes.contains(&p) {
-                            continue;
-                        }
-                        clone.visited_slopes.push(p);
+#[derive(Debug)]
+struct Maze {
+    cells: Vec<&'static [u8]>,
+}
 
-                        Point::new(p.row, p.col + 1)
-                    }
-                    b'<' => {
-                        if clone.visited_slopes.contains(&p) {
-                            continue;
-                        }
-                        clone.visited_slopes.push(p);
-
-                        Point::new(p.row, p.col - 1)
-                    }
-                    b'.' => {
-                        next_steps -= 1;
-                        p
-                    }
-                    invalid => unreachable!("Invalid byte: '{invalid}'"),
-                };
-
-                clone.prev = clone.curr;
-                clone.curr = next_point;
-                queue.push_back((next_steps, clone));
-            }
-        }
+impl From<&'static str> for Maze {
+    fn from(value: &'static str) -> Self {
+        let cells = value.lines().map(|line| line.as_bytes()).collect();
 
-        max_steps
+        Self { cells }
     }
+}
 
-    fn get_next_moves_no_slope(&self, point: &Point) -> Vec<Point> {
-        let mut points = Vec::new();
-        if point.row > 0 {
-            points.push(Point::new(point.row - 1, point.col));
-        }
-        if point.row < self.cells.len() - 1 {
-            points.push(Point::new(point.row + 1, point.col));
-        }
-
-        if point.col > 0 {
-            points.push(Point::new(point.row, point.col - 1));
-        }
+impl Maze {
+    fn get_content(&self, p: &Point) -> u8 {
+        self.cells[p.row][p.col]
+    }
 
-        if point.col < self.cells[0].len() - 1 {
-            points.push(Point::new(point.row, point.col + 1));
-        }
+    fn get_neighbor(&self, point: &Point, dir: Dir) -> Option<Point> {
+        let neighbor = match dir {
+            Dir::Up => Point::new(point.row.checked_sub(1)?, point.col),
+            Dir::Down => Point::new(point.row + 1, point.col),
+            Dir::Left => Point::new(point.row, point.col.checked_sub(1)?),
+            Dir::Right => Point::new(point.row, point.col + 1),
+        };
+
+        (neighbor.row < self.cells.len()
+            && neighbor.col < self.cells[0].len()
+            && self.get_content(&neighbor) != b'#')
+            .then_some(neighbor)
+    }
 
-        points
+    /// The open neighbours. With slopes, a slope can only be left and entered in its own
+    /// direction.
+    fn get_next_moves(&self, point: &Point, slopes: bool) -> Vec<Point> {
+        let allowed = |content: u8, dir: Dir| {
+            !slopes || Dir::from_slope(content).is_none_or(|slope| slope == dir)
+        };
+        Dir::ALL
             .into_iter()
-            .filter(|p| self.get_content(p) != b'#')
+            .filter(|dir| allowed(self.get_content(point), *dir))
+            .filter_map(|dir| self.get_neighbor(point, dir).map(|next| (next, dir)))
+            .filter(|(next, dir)| allowed(self.get_content(next), *dir))
+            .map(|(next, _)| next)
             .collect()
     }
 
-    fn get_longest_dist_recr(
-        start: &Point,
-        end: &Point,
-        map: &HashMap<Point, HashMap<Point, usize>>,
-        visited: &HashSet<Point>,
-    ) -> usize {
-        if start == end {
-            return 0;
-        }
-
-        let mut visited_clone = visited.clone();
-        visited_clone.insert(*start);
-
-        let mut distance = 0_usize;
-        let start_node_map = map.get(start).unwrap();
-        for neighbor in start_node_map.keys() {
-            if !visited_clone.contains(neighbor) {
-                let next_dist = Self::get_longest_dist_recr(neighbor, end, map, &visited_clone);
-                distance = distance.max(next_dist + *start_node_map.get(neighbor).unwrap());
-            }
-        }
-
-        distance
-    }
-
-    fn find_max_steps_graph(&self) -> usize {
+    /// Compresses the maze to the start, the end and the junctions, connected by the corridors
+    /// between them. The slopes make the corridors one way.
+    fn build_graph(&self, slopes: bool) -> Graph {
         let start_pos = Point::new(0, 1);
         let end_pos = Point::new(self.cells.len() - 1, self.cells[0].len() - 2);
 
-        // Define the node points so we can fill them in the graph below
-        let mut node_points = vec![start_pos, end_pos];
-        let nodes = (0..self.cells.len())
+        let junctions = (0..self.cells.len())
             .flat_map(|row| (0..self.cells[0].len()).map(move |col| Point::new(row, col)))
-            .filter(|p| self.get_content(p) != b'#' && self.get_next_moves_no_slope(p).len() > 2);
-
-        node_points.extend(nodes);
-
-        // Fill the Graph with distances
-        let mut graph: HashMap<Point, HashMap<Point, usize>> =
-            node_points.iter().map(|p| (*p, HashMap::new())).collect();
-
-        for node in &node_points {
-            let mut queue: Vec<(Point, usize)> = vec![(*node, 0)];
-            let mut visited: HashSet<Point> = HashSet::new();
-
-            while let Some((curr_point, curr_dist)) = queue.pop() {
-                if node_points.contains(&curr_point) && curr_dist != 0 {
-                    graph
-                        .entry(*node)
-                        .or_default()
-                        .entry(curr_point)
-                        .or_insert(curr_dist);
-                    continue;
-                }
-
-                for next_point in self.get_next_moves_no_slope(&curr_point) {
-                    if visited.insert(next_point) {
-                        queue.push((next_point, curr_dist + 1));
-                    }
-                }
-
-                visited.insert(curr_point);
-            }
-        }
+            .filter(|p| self.get_content(p) != b'#' && self.get_next_moves(p, false).len() > 2);
+        let node_points: Vec<Point> = [start_pos, end_pos].into_iter().chain(junctions).collect();
+        let ids: HashMap<Point, usize> = node_points
+            .iter()
+            .enumerate()
+            .map(|(id, point)| (*point, id))
+            .collect();
+
+        let edges = node_points
+            .iter()
+            .map(|node| {
+                self.get_next_moves(node, slopes)
+                    .into_iter()
+                    .filter_map(|first| {
+                        // Follow the corridor until the next node, giving up on dead ends and
+                        // slopes against the walking direction.
+                        let (mut prev, mut curr, mut steps) = (*node, first, 1);
+                        while !ids.contains_key(&curr) {
+                            let next = self
+                                .get_next_moves(&curr, slopes)
+                                .into_iter()
+                                .find(|next| *next != prev)?;
+                            (prev, curr, steps) = (curr, next, steps + 1);
+                        }
 
-        // Use the graph to find the answer recursively
-        Self::get_longest_dist_recr(&start_pos, &end_pos, &graph, &HashSet::new())
+                        Some((ids[&curr], steps))
+                    })
+                    .collect()
+            })
+            .collect();
+
+        Graph {
+            edges,
+            start: ids[&start_pos],
+            end: ids[&end_pos],
+        }
     }
 }
 
 fn find_max_steps(input: &'static str) -> usize {
     let maze = Maze::from(input);
 
-    maze.find_max_steps()
+    maze.build_graph(true)
+        .longest_path()
+        .expect("no path to the exit")
 }
 
 fn find_max_no_slope(input: &'static str) -> usize {
     let maze = Maze::from(input);
 
-    maze.find_max_steps_graph()
+    maze.build_graph(false)
+        .longest_path()
+        .expect("no path to the exit")
 }
 
 fn part_1(input: &'static str) {
@@ -310,4 +255,21 @@ mod test {
         assert_eq!(find_max_steps(INPUT), 94);
         assert_eq!(find_max_no_slope(INPUT), 154);
     }
+
+    #[test]
+    fn test_graph() {
+        let maze = Maze::from(INPUT);
+        let edges_count = |graph: &Graph| graph.edges.iter().map(Vec::len).sum::<usize>();
+
+        // The slopes keep one direction of each corridor.
+        let directed = maze.build_graph(true);
+        let undirected = maze.build_graph(false);
+        assert_eq!(directed.edges.len(), 9);
+        assert_eq!(edges_count(&directed), 12);
+        assert_eq!(edges_count(&undirected), 24);
+        assert!(directed.edges[directed.end].is_empty());
+
+        assert_eq!(directed.last_junction(), Some((8, 5)));
+        assert_eq!(undirected.last_junction(), Some((8, 5)));
+    }
 }
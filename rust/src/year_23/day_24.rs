@@ -1,3 +1,7 @@
+use num_bigint::BigInt;
+use num_rational::BigRational;
+use num_traits::{ToPrimitive, Zero};
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 struct Point {
     x: i128,
@@ -9,6 +13,31 @@ impl Point {
     fn new(x: i128, y: i128, z: i128) -> Self {
         Self { x, y, z }
     }
+
+    fn sub(self, other: Point) -> Point {
+        Point::new(self.x - other.x, self.y - other.y, self.z - other.z)
+    }
+
+    fn cross(self, other: Point) -> Point {
+        Point::new(
+            self.y * other.z - self.z * other.y,
+            self.z * other.x - self.x * other.z,
+            self.x * other.y - self.y * other.x,
+        )
+    }
+
+    fn is_zero(self) -> bool {
+        self.x == 0 && self.y == 0 && self.z == 0
+    }
+
+    /// Coefficients of the components of `a` in `a x self`, one row per component of the result.
+    fn cross_left_coefs(self) -> [[i128; 3]; 3] {
+        [
+            [0, self.z, -self.y],
+            [-self.z, 0, self.x],
+            [self.y, -self.x, 0],
+        ]
+    }
 }
 
 impl From<&'static str> for Point {
@@ -110,8 +139,123 @@ fn part_1(input: &'static str) {
     println!("Part 1 answer is {answer}");
 }
 
-fn part_2(_input: &'static str) {
-    println!("Part 2 answer is solved using z3 solver in python. the Answer is 566373506408017");
+/// Solves the system given as augmented matrix rows using exact rational Gaussian elimination.
+/// Returns None if the system doesn't have a unique solution.
+fn solve_exact(rows: Vec<Vec<i128>>) -> Option<Vec<BigRational>> {
+    let unknowns = rows[0].len() - 1;
+    let mut rows: Vec<Vec<BigRational>> = rows
+        .into_iter()
+        .map(|row| {
+            row.into_iter()
+                .map(|num| BigRational::from_integer(BigInt::from(num)))
+                .collect()
+        })
+        .collect();
+
+    for col in 0..unknowns {
+        let pivot = (col..rows.len()).find(|&row| !rows[row][col].is_zero())?;
+        rows.swap(col, pivot);
+
+        let pivot_row = rows[col].clone();
+        for (idx, row) in rows.iter_mut().enumerate() {
+            if idx == col || row[col].is_zero() {
+                continue;
+            }
+            let factor = &row[col] / &pivot_row[col];
+            for (cell, pivot_cell) in row.iter_mut().zip(&pivot_row).skip(col) {
+                *cell -= &factor * pivot_cell;
+            }
+        }
+    }
+
+    Some(
+        (0..unknowns)
+            .map(|col| &rows[col][unknowns] / &rows[col][col])
+            .collect(),
+    )
+}
+
+/// Each hail `i` gets hit when `(P - p_i) x (V - v_i) = 0`. Subtracting the equations of two
+/// hails removes the non-linear `P x V` part and leaves three linear equations:
+/// `P x (v_j - v_i) + (p_j - p_i) x V = p_j x v_j - p_i x v_i`
+fn linear_rows(first: &Hail, second: &Hail) -> Vec<Vec<i128>> {
+    let pos_coefs = second.velocity.sub(first.velocity).cross_left_coefs();
+    // e x V = -(V x e)
+    let vel_coefs = second.position.sub(first.position).cross_left_coefs();
+    let rhs = second
+        .position
+        .cross(second.velocity)
+        .sub(first.position.cross(first.velocity));
+
+    (0..3)
+        .map(|idx| {
+            let mut row = pos_coefs[idx].to_vec();
+            row.extend(vel_coefs[idx].iter().map(|coef| -coef));
+            row.push([rhs.x, rhs.y, rhs.z][idx]);
+            row
+        })
+        .collect()
+}
+
+fn hits_all(position: Point, velocity: Point, hails: &[Hail]) -> bool {
+    hails.iter().all(|hail| {
+        let offset = hail.position.sub(position);
+        let relative = velocity.sub(hail.velocity);
+        if !offset.cross(relative).is_zero() {
+            return false;
+        }
+
+        // The hit must happen in the future on the common line.
+        [
+            (offset.x, relative.x),
+            (offset.y, relative.y),
+            (offset.z, relative.z),
+        ]
+        .into_iter()
+        .all(|(offset, relative)| relative == 0 || offset * relative.signum() >= 0)
+    })
+}
+
+/// Finds the position and velocity of the rock hitting all hails.
+fn find_throw(hails: &[Hail]) -> Option<(Point, Point)> {
+    let base = &hails[0];
+    for j in 1..hails.len() {
+        for k in j + 1..hails.len() {
+            let mut rows = linear_rows(base, &hails[j]);
+            rows.extend(linear_rows(base, &hails[k]));
+
+            let Some(solution) = solve_exact(rows) else {
+                continue;
+            };
+            if solution.iter().any(|num| !num.is_integer()) {
+                return None;
+            }
+
+            let nums: Vec<i128> = solution
+                .iter()
+                .map(|num| num.to_integer().to_i128().unwrap())
+                .collect();
+            let position = Point::new(nums[0], nums[1], nums[2]);
+            let velocity = Point::new(nums[3], nums[4], nums[5]);
+
+            return hits_all(position, velocity, hails).then_some((position, velocity));
+        }
+    }
+
+    None
+}
+
+fn calc_throw_sum(input: &'static str) -> i128 {
+    let hails: Vec<_> = input.lines().map(Hail::from).collect();
+    let (position, _) = find_throw(&hails).expect("no throw hits all hails");
+
+    position.x + position.y + position.z
+}
+
+fn part_2(input: &'static str) {
+    let answer = calc_throw_sum(input);
+
+    println!("Part 2 answer is {answer}");
 }
 
 pub fn run() {
@@ -133,5 +277,12 @@ mod test {
     #[test]
     fn test_solution() {
         assert_eq!(find_inter_count(INPUT, 7, 27), 2);
+        assert_eq!(calc_throw_sum(INPUT), 47);
+
+        let hails: Vec<_> = INPUT.lines().map(Hail::from).collect();
+        let (position, velocity) = find_throw(&hails).unwrap();
+        assert_eq!(position, Point::new(24, 13, 10));
+        assert_eq!(velocity, Point::new(-3, 1, 2));
+        assert!(!hits_all(position, Point::new(-3, 1, 3), &hails));
     }
 }
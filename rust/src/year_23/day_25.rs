@@ -22,70 +22,156 @@ fn get_uniqe_key(n1: &'static str, n2: &'static str) -> [&'static str; 2] {
     if n1 > n2 { [n1, n2] } else { [n2, n1] }
 }
 
-fn solve(input: &'static str) -> usize {
-    let edges = parse_edges(input);
-    // Let's assume that the target cuts are the connections with the highest frequency
-    // This assumtion is valid for the real input but not for the example
+/// Undirected graph with unit capacities, where the flow of each edge goes from `from` to `to`
+/// when positive and in the other direction when negative.
+struct FlowGraph {
+    names: Vec<&'static str>,
+    edges: Vec<(usize, usize)>,
+    // Per node: (neighbour, edge index)
+    adjacent: Vec<Vec<(usize, usize)>>,
+}
+
+impl FlowGraph {
+    fn new(connections: &HashMap<&'static str, HashSet<&'static str>>) -> Self {
+        let mut names: Vec<_> = connections.keys().copied().collect();
+        names.sort_unstable();
+        let ids: HashMap<_, _> = names.iter().enumerate().map(|(i, &n)| (n, i)).collect();
+
+        let mut edges = Vec::new();
+        let mut adjacent = vec![Vec::new(); names.len()];
+        for (&node, nexts) in connections {
+            for &next in nexts {
+                let (from, to) = (ids[node], ids[next]);
+                if from < to {
+                    adjacent[from].push((to, edges.len()));
+                    adjacent[to].push((from, edges.len()));
+                    edges.push((from, to));
+                }
+            }
+        }
 
-    let mut freq_map = HashMap::new();
+        Self {
+            names,
+            edges,
+            adjacent,
+        }
+    }
 
-    for &edge in edges.keys() {
-        let mut queue = VecDeque::new();
-        queue.push_back(edge);
-        let mut visited = HashSet::new();
+    fn residual(&self, flows: &[i32], node: usize, edge: usize) -> i32 {
+        if self.edges[edge].0 == node {
+            1 - flows[edge]
+        } else {
+            1 + flows[edge]
+        }
+    }
 
+    /// Nodes reachable from the source in the residual graph.
+    fn reachable(&self, flows: &[i32], source: usize) -> Vec<bool> {
+        let mut visited = vec![false; self.names.len()];
+        visited[source] = true;
+        let mut queue = VecDeque::from([source]);
         while let Some(node) = queue.pop_front() {
-            for next in edges.get(node).unwrap() {
-                if !visited.insert(next) {
-                    continue;
+            for &(next, edge) in &self.adjacent[node] {
+                if !visited[next] && self.residual(flows, node, edge) > 0 {
+                    visited[next] = true;
+                    queue.push_back(next);
                 }
+            }
+        }
 
-                let key = get_uniqe_key(node, next);
+        visited
+    }
 
-                freq_map
-                    .entry(key)
-                    .and_modify(|c| *c += 1)
-                    .or_insert(1_usize);
+    /// Edmonds-Karp max flow which stops once the flow reaches the limit.
+    fn max_flow(&self, source: usize, sink: usize, limit: usize) -> (usize, Vec<i32>) {
+        let mut flows = vec![0; self.edges.len()];
+        let mut flow = 0;
+
+        while flow < limit {
+            let mut prev: Vec<Option<(usize, usize)>> = vec![None; self.names.len()];
+            let mut queue = VecDeque::from([source]);
+            while let Some(node) = queue.pop_front() {
+                if node == sink {
+                    break;
+                }
+                for &(next, edge) in &self.adjacent[node] {
+                    if next != source
+                        && prev[next].is_none()
+                        && self.residual(&flows, node, edge) > 0
+                    {
+                        prev[next] = Some((node, edge));
+                        queue.push_back(next);
+                    }
+                }
+            }
 
-                queue.push_back(next);
+            if prev[sink].is_none() {
+                break;
             }
+
+            let mut node = sink;
+            while let Some((from, edge)) = prev[node] {
+                flows[edge] += if self.edges[edge].0 == from { 1 } else { -1 };
+                node = from;
+            }
+            flow += 1;
         }
-    }
 
-    let mut map_vec: Vec<_> = freq_map.iter().collect();
-    map_vec.sort_unstable_by_key(|e| e.1);
-    let cutted: Vec<_> = map_vec.iter().rev().take(3).map(|p| *p.0).collect();
+        (flow, flows)
+    }
+}
 
-    // find the count from any edge with cutting the three links
-    let start = *edges.keys().next().unwrap();
-    let mut size = 1;
+#[derive(Debug)]
+struct MinCut {
+    edges: Vec<[&'static str; 2]>,
+    sizes: (usize, usize),
+}
 
-    let mut queue = VecDeque::new();
-    queue.push_back(start);
+/// Global minimum cut: It separates the first node from some other node, so the smallest of the
+/// max flows between the first node and each other one gives the cut.
+fn find_min_cut(input: &'static str) -> MinCut {
+    let graph = FlowGraph::new(&parse_edges(input));
+    let source = 0;
+
+    let mut best: Option<(usize, usize)> = None;
+    for sink in 1..graph.names.len() {
+        let limit = best.map_or(usize::MAX, |(flow, _)| flow);
+        let (flow, _) = graph.max_flow(source, sink, limit);
+        if flow < limit {
+            best = Some((flow, sink));
+        }
+    }
 
-    let mut visited = HashSet::new();
-    visited.insert(start);
+    let (_, sink) = best.expect("graph needs at least two nodes");
+    let (_, flows) = graph.max_flow(source, sink, usize::MAX);
+    let side = graph.reachable(&flows, source);
 
-    while let Some(node) = queue.pop_front() {
-        for &next in edges.get(node).unwrap() {
-            let key = get_uniqe_key(node, next);
+    let mut edges: Vec<_> = graph
+        .edges
+        .iter()
+        .filter(|&&(from, to)| side[from] != side[to])
+        .map(|&(from, to)| get_uniqe_key(graph.names[from], graph.names[to]))
+        .collect();
+    edges.sort_unstable();
 
-            if cutted.contains(&key) {
-                continue;
-            }
+    let size = side.iter().filter(|&&s| s).count();
 
-            if visited.insert(next) {
-                size += 1;
-                queue.push_back(next);
-            }
-        }
+    MinCut {
+        edges,
+        sizes: (size, graph.names.len() - size),
     }
-
-    size * (edges.len() - size)
 }
 
 fn part_1(input: &'static str) {
-    let answer = solve(input);
+    let cut = find_min_cut(input);
+    let edges: Vec<_> = cut
+        .edges
+        .iter()
+        .map(|[n1, n2]| format!("{n1}/{n2}"))
+        .collect();
+    println!("Cut edges are {}", edges.join(", "));
+
+    let answer = cut.sizes.0 * cut.sizes.1;
 
     println!("Par 1 answer is {answer}");
 }
@@ -99,3 +185,30 @@ pub fn run() {
     part_1(input);
     part_2(input);
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const INPUT: &str = "jqt: rhn xhk nvd
+rsh: frs pzl lsr
+xhk: hfx
+cmg: qnr nvd lhk bvb
+rhn: xhk bvb hfx
+bvb: xhk hfx
+pzl: lsr hfx nvd
+qnr: nvd
+ntq: jqt hfx bvb xhk
+nvd: lhk
+lsr: lhk
+rzs: qnr cmg lsr rsh
+frs: qnr lhk lsr";
+
+    #[test]
+    fn test_solution() {
+        let cut = find_min_cut(INPUT);
+        assert_eq!(cut.edges, [["cmg", "bvb"], ["nvd", "jqt"], ["pzl", "hfx"]]);
+        assert_eq!(cut.sizes.0 + cut.sizes.1, 15);
+        assert_eq!(cut.sizes.0 * cut.sizes.1, 54);
+    }
+}
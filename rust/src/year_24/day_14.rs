@@ -1,7 +1,9 @@
-use std::{collections::HashSet, io};
+use std::collections::HashSet;
 
 use rayon::iter::{IntoParallelRefMutIterator, ParallelIterator};
 
+use crate::image::{self, Image};
+
 #[derive(Debug, Clone)]
 struct Robot {
     x: isize,
@@ -32,6 +34,13 @@ impl Robot {
         self.x = add_wrapping(self.x, self.dx, max_x);
         self.y = add_wrapping(self.y, self.dy, max_y);
     }
+
+    fn pos_at(&self, sec: isize, max_x: isize, max_y: isize) -> (isize, isize) {
+        (
+            (self.x + self.dx * sec).rem_euclid(max_x),
+            (self.y + self.dy * sec).rem_euclid(max_y),
+        )
+    }
 }
 
 #[inline]
@@ -87,36 +96,78 @@ fn part_1(input: &'static str) {
     println!("Part 1 answer is {ans}");
 }
 
-// Print the steps where there are not overlapping in the robots.
-fn print_unique_steps(input: &str, max_x: isize, max_y: isize) {
-    let mut robots = parse(input);
-    let mut sec = 0;
-    let mut buffer = String::new();
-    loop {
-        robots.iter_mut().for_each(|r| r.mov(max_x, max_y));
-        sec += 1;
-        let set: HashSet<(isize, isize)> = HashSet::from_iter(robots.iter().map(|r| (r.x, r.y)));
-        if set.len() != robots.len() {
-            continue;
-        }
+/// Variance of the values multiplied by count², which keeps it in integers.
+fn scaled_variance(values: impl Iterator<Item = isize>) -> isize {
+    let (count, sum, sum_sq) = values.fold((0, 0, 0), |(count, sum, sum_sq), val| {
+        (count + 1, sum + val, sum_sq + val * val)
+    });
+
+    count * sum_sq - sum * sum
+}
+
+/// Returns the second within one period of the axis where the robots are the closest together.
+fn min_variance_sec(
+    robots: &[Robot],
+    period: isize,
+    axis: impl Fn(&Robot, isize) -> isize,
+) -> isize {
+    (0..period)
+        .min_by_key(|&sec| scaled_variance(robots.iter().map(|r| axis(r, sec))))
+        .unwrap()
+}
+
+/// The robots form the picture when they are clustered on both axes. Each axis repeats on its
+/// own period, so the best second of each one gets combined with the chinese remainder theorem.
+fn find_tree_sec(input: &str, max_x: isize, max_y: isize) -> isize {
+    let robots = parse(input);
+    let sec_x = min_variance_sec(&robots, max_x, |r, sec| r.pos_at(sec, max_x, max_y).0);
+    let sec_y = min_variance_sec(&robots, max_y, |r, sec| r.pos_at(sec, max_x, max_y).1);
+
+    (0..max_y)
+        .map(|k| sec_x + k * max_x)
+        .find(|sec| sec % max_y == sec_y)
+        .expect("grid sizes must be coprime")
+}
+
+#[allow(unused)]
+fn render_frame(input: &str, sec: isize, max_x: isize, max_y: isize) -> String {
+    let positions: HashSet<_> = parse(input)
+        .iter()
+        .map(|r| r.pos_at(sec, max_x, max_y))
+        .collect();
+
+    let mut frame = String::new();
+    for y in 0..max_y {
         for x in 0..max_x {
-            for y in 0..max_y {
-                if set.contains(&(x, y)) {
-                    print!("#");
-                } else {
-                    print!(".");
-                }
-            }
-            println!()
+            frame.push(if positions.contains(&(x, y)) {
+                '#'
+            } else {
+                '.'
+            });
         }
+        frame.push('\n');
+    }
+
+    frame
+}
 
-        println!("{sec}");
-        io::stdin().read_line(&mut buffer).unwrap();
+const TREE_SCALE: usize = 4;
+
+fn tree_image(input: &str, sec: isize, max_x: isize, max_y: isize) -> Image {
+    let mut image = Image::new(max_x as usize, max_y as usize);
+    for robot in parse(input) {
+        let (x, y) = robot.pos_at(sec, max_x, max_y);
+        image.set(x as usize, y as usize, image::WHITE);
     }
+
+    image.scale(TREE_SCALE)
 }
 
 fn part_2(input: &'static str) {
-    print_unique_steps(input, 101, 103);
+    let ans = find_tree_sec(input, 101, 103);
+    println!("Part 2 answer is {ans}");
+
+    image::export("24_14_tree", || tree_image(input, ans, 101, 103));
 }
 
 pub fn run() {
@@ -147,4 +198,35 @@ p=9,5 v=-3,-3";
         let ans = calc_pos_count(INPUT, 11, 7);
         assert_eq!(ans, 12);
     }
+
+    #[test]
+    fn test_find_tree_sec() {
+        let (max_x, max_y, target_sec) = (101, 103, 6_000);
+
+        // Robots scattered everywhere besides a small block at the target second.
+        let mut seed = 7_isize;
+        let mut next = |max: isize| {
+            seed = (seed * 1_103_515_245 + 12_345).rem_euclid(1 << 31);
+            seed % max
+        };
+        let mut lines = Vec::new();
+        for idx in 0..300 {
+            let (dx, dy) = (next(max_x * 2) - max_x, next(max_y * 2) - max_y);
+            let (x, y) = if idx < 150 {
+                (40 + next(10), 50 + next(10))
+            } else {
+                (next(max_x), next(max_y))
+            };
+            let start_x = (x - dx * target_sec).rem_euclid(max_x);
+            let start_y = (y - dy * target_sec).rem_euclid(max_y);
+            lines.push(format!("p={start_x},{start_y} v={dx},{dy}"));
+        }
+        let input = lines.join("\n");
+
+        assert_eq!(find_tree_sec(&input, max_x, max_y), target_sec);
+
+        let frame = render_frame(INPUT, 0, 11, 7);
+        assert_eq!(frame.lines().count(), 7);
+        assert_eq!(frame.lines().next().unwrap(), "#.##.......");
+    }
 }
@@ -1,4 +1,10 @@
-use std::collections::HashSet;
+use std::{
+    collections::HashSet,
+    env,
+    io::{self, Write},
+};
+
+use crate::{anim, utls::find_arg_value};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 struct Pos {
@@ -103,7 +109,9 @@ fn parse(input: &str) -> (Vec<Vec<Slot>>, Pos, Vec<Dir>) {
     (grid, start.unwrap(), moves)
 }
 
-fn apply_move(grid: &mut [Vec<Slot>], robot: Pos, dir: Dir) -> Pos {
+/// Moves the robot if possible, returning its new position and the positions of the pushed box
+/// cells before the move.
+fn apply_move(grid: &mut [Vec<Slot>], robot: Pos, dir: Dir) -> (Pos, Vec<Pos>) {
     let mut to_move = vec![];
     let mut do_move = false;
     let mut current = robot;
@@ -122,7 +130,7 @@ fn apply_move(grid: &mut [Vec<Slot>], robot: Pos, dir: Dir) -> Pos {
     }
 
     if !do_move {
-        return robot;
+        return (robot, Vec::new());
     }
 
     for slot in to_move.iter().rev() {
@@ -135,14 +143,18 @@ fn apply_move(grid: &mut [Vec<Slot>], robot: Pos, dir: Dir) -> Pos {
     grid[robot.row][robot.col] = Slot::Empty;
     grid[next_robot.row][next_robot.col] = Slot::Robot;
 
-    next_robot
+    (next_robot, to_move)
 }
 
 fn sum_gps_coor(input: &str) -> usize {
     let (mut grid, mut robot, moves) = parse(input);
 
-    for dir in moves {
-        robot = apply_move(&mut grid, robot, dir);
+    let total = moves.len();
+    for (idx, dir) in moves.into_iter().enumerate() {
+        robot = apply_move(&mut grid, robot, dir).0;
+        anim::frame(format_args!("Move {}/{total}: {dir:?}", idx + 1), || {
+            render_grid(&grid)
+        });
     }
 
     let mut sum = 0;
@@ -158,8 +170,8 @@ fn sum_gps_coor(input: &str) -> usize {
     sum
 }
 
-#[allow(unused)]
-fn print_grid(grid: &[Vec<Slot>]) {
+fn render_grid(grid: &[Vec<Slot>]) -> String {
+    let mut frame = String::new();
     for row in grid {
         for item in row {
             let ch = match item {
@@ -168,10 +180,17 @@ fn print_grid(grid: &[Vec<Slot>]) {
                 Slot::Box => 'O',
                 Slot::Robot => '@',
             };
-            print!("{ch}");
+            frame.push(ch);
         }
-        println!();
+        frame.push('\n');
     }
+
+    frame
+}
+
+#[allow(unused)]
+fn print_grid(grid: &[Vec<Slot>]) {
+    print!("{}", render_grid(grid));
 }
 
 fn part_1(input: &'static str) {
@@ -198,7 +217,8 @@ fn widen_grid(grid: &[Vec<Slot>]) -> Vec<Vec<SlotWiden>> {
     g
 }
 
-fn apply_move_widen(grid: &mut [Vec<SlotWiden>], robot: Pos, dir: Dir) -> Pos {
+/// Same as [`apply_move()`] for the widened grid, where the pushed cells contain both box halves.
+fn apply_move_widen(grid: &mut [Vec<SlotWiden>], robot: Pos, dir: Dir) -> (Pos, Vec<Pos>) {
     let horizontal = matches!(dir, Dir::Left | Dir::Right);
     if horizontal {
         let mut to_move = vec![];
@@ -219,7 +239,7 @@ fn apply_move_widen(grid: &mut [Vec<SlotWiden>], robot: Pos, dir: Dir) -> Pos {
         }
 
         if !do_move {
-            return robot;
+            return (robot, Vec::new());
         }
 
         for slot in to_move.iter().rev() {
@@ -232,7 +252,7 @@ fn apply_move_widen(grid: &mut [Vec<SlotWiden>], robot: Pos, dir: Dir) -> Pos {
         grid[robot.row][robot.col] = SlotWiden::Empty;
         grid[next_robot.row][next_robot.col] = SlotWiden::Robot;
 
-        return next_robot;
+        return (next_robot, to_move);
     }
 
     // Vertical
@@ -299,7 +319,7 @@ fn apply_move_widen(grid: &mut [Vec<SlotWiden>], robot: Pos, dir: Dir) -> Pos {
     }
 
     if !do_move {
-        return robot;
+        return (robot, Vec::new());
     }
 
     for slot in to_move.iter().rev() {
@@ -314,26 +334,36 @@ fn apply_move_widen(grid: &mut [Vec<SlotWiden>], robot: Pos, dir: Dir) -> Pos {
     grid[robot.row][robot.col] = SlotWiden::Empty;
     grid[next_robot.row][next_robot.col] = SlotWiden::Robot;
 
-    next_robot
-}
-
-fn sum_gps_widen(input: &str) -> usize {
-    let (grid, _, moves) = parse(input);
+    // The robot is moved with the boxes when its next slot is empty.
+    to_move.retain(|pos| *pos != robot);
 
-    let mut grid = widen_grid(&grid);
+    (next_robot, to_move)
+}
 
-    let mut robot = grid
-        .iter()
+fn find_robot_widen(grid: &[Vec<SlotWiden>]) -> Pos {
+    grid.iter()
         .enumerate()
         .find_map(|(ridx, row)| {
             row.iter()
                 .position(|&s| s == SlotWiden::Robot)
                 .map(|col| Pos::new(ridx, col))
         })
-        .unwrap();
+        .unwrap()
+}
+
+fn sum_gps_widen(input: &str) -> usize {
+    let (grid, _, moves) = parse(input);
+
+    let mut grid = widen_grid(&grid);
+
+    let mut robot = find_robot_widen(&grid);
 
-    for dir in moves {
-        robot = apply_move_widen(&mut grid, robot, dir);
+    let total = moves.len();
+    for (idx, dir) in moves.into_iter().enumerate() {
+        robot = apply_move_widen(&mut grid, robot, dir).0;
+        anim::frame(format_args!("Move {}/{total}: {dir:?}", idx + 1), || {
+            render_widen(&grid)
+        });
     }
 
     let mut sum = 0;
@@ -349,8 +379,8 @@ fn sum_gps_widen(input: &str) -> usize {
     sum
 }
 
-#[allow(unused)]
-fn print_widen(grid: &[Vec<SlotWiden>]) {
+fn render_widen(grid: &[Vec<SlotWiden>]) -> String {
+    let mut frame = String::new();
     for row in grid {
         for item in row {
             let ch = match item {
@@ -360,9 +390,193 @@ fn print_widen(grid: &[Vec<SlotWiden>]) {
                 SlotWiden::BoxRight => ']',
                 SlotWiden::Robot => '@',
             };
-            print!("{ch}");
+            frame.push(ch);
+        }
+        frame.push('\n');
+    }
+
+    frame
+}
+
+#[allow(unused)]
+fn print_widen(grid: &[Vec<SlotWiden>]) {
+    print!("{}", render_widen(grid));
+}
+
+/// Moves between two saved grid states in the replay.
+const CHECKPOINT_STEP: usize = 100;
+
+type MoveFn<S> = fn(&mut [Vec<S>], Pos, Dir) -> (Pos, Vec<Pos>);
+
+/// Replays the moves of the robot forward and backward. The grid is saved every
+/// [`CHECKPOINT_STEP`] moves, so going back restores the last saved grid before the target move
+/// and applies the moves from there.
+struct Replay<S> {
+    moves: Vec<Dir>,
+    apply: MoveFn<S>,
+    render: fn(&[Vec<S>]) -> String,
+    checkpoints: Vec<(Vec<Vec<S>>, Pos)>,
+    grid: Vec<Vec<S>>,
+    robot: Pos,
+    applied: usize,
+    /// Box cells pushed by the last applied move at their new positions.
+    pushed: Vec<Pos>,
+}
+
+impl<S: Clone> Replay<S> {
+    fn new(
+        grid: Vec<Vec<S>>,
+        robot: Pos,
+        moves: Vec<Dir>,
+        apply: MoveFn<S>,
+        render: fn(&[Vec<S>]) -> String,
+    ) -> Self {
+        Self {
+            moves,
+            apply,
+            render,
+            checkpoints: vec![(grid.clone(), robot)],
+            grid,
+            robot,
+            applied: 0,
+            pushed: Vec::new(),
+        }
+    }
+
+    fn forward(&mut self) -> bool {
+        let Some(&dir) = self.moves.get(self.applied) else {
+            return false;
+        };
+
+        let (robot, pushed) = (self.apply)(&mut self.grid, self.robot, dir);
+        self.robot = robot;
+        self.pushed = pushed.iter().map(|pos| dir.next(pos)).collect();
+        self.applied += 1;
+
+        if self.applied.is_multiple_of(CHECKPOINT_STEP)
+            && self.checkpoints.len() == self.applied / CHECKPOINT_STEP
+        {
+            self.checkpoints.push((self.grid.clone(), self.robot));
+        }
+
+        true
+    }
+
+    fn back(&mut self) -> bool {
+        if self.applied == 0 {
+            return false;
+        }
+
+        self.jump(self.applied - 1);
+        true
+    }
+
+    /// Goes to the state after applying the given count of moves.
+    fn jump(&mut self, target: usize) {
+        let target = target.min(self.moves.len());
+        if target < self.applied {
+            // Start one move earlier to get the pushed boxes of the target move.
+            let checkpoint = target.saturating_sub(1) / CHECKPOINT_STEP;
+            let (grid, robot) = &self.checkpoints[checkpoint];
+            self.grid = grid.clone();
+            self.robot = *robot;
+            self.applied = checkpoint * CHECKPOINT_STEP;
+            self.pushed.clear();
+        }
+
+        while self.applied < target {
+            self.forward();
+        }
+    }
+
+    /// Renders the grid with the boxes pushed by the last move highlighted.
+    fn frame(&self) -> String {
+        let last_move = match self.applied {
+            0 => String::from("Start"),
+            applied => format!("{:?}", self.moves[applied - 1]),
+        };
+        let mut frame = format!(
+            "Move {}/{}: {last_move}\nPushed boxes: {:?}\n",
+            self.applied,
+            self.moves.len(),
+            self.pushed
+                .iter()
+                .map(|pos| (pos.row, pos.col))
+                .collect::<Vec<_>>()
+        );
+
+        let grid = (self.render)(&self.grid);
+        for (row, line) in grid.lines().enumerate() {
+            for (col, ch) in line.chars().enumerate() {
+                if self.pushed.contains(&Pos::new(row, col)) {
+                    frame.push_str(&format!("\x1b[1;33m{ch}\x1b[0m"));
+                } else {
+                    frame.push(ch);
+                }
+            }
+            frame.push('\n');
+        }
+
+        frame
+    }
+}
+
+/// Runs the replay reading the commands from stdin.
+fn run_replay<S: Clone>(replay: &mut Replay<S>) {
+    let stdin = io::stdin();
+    loop {
+        println!("{}", replay.frame());
+        print!("[Enter/n] next, [p] previous, [j <move>] jump, [q] quit: ");
+        io::stdout().flush().unwrap();
+
+        let mut command = String::new();
+        if stdin.read_line(&mut command).unwrap() == 0 {
+            return;
+        }
+
+        match command.split_whitespace().collect::<Vec<_>>().as_slice() {
+            [] | ["n"] => {
+                if !replay.forward() {
+                    println!("Reached the last move");
+                }
+            }
+            ["p"] => {
+                if !replay.back() {
+                    println!("Already at the start");
+                }
+            }
+            ["j", target] => match target.parse() {
+                Ok(target) => replay.jump(target),
+                Err(err) => println!("Invalid move index: {err}"),
+            },
+            ["q"] => return,
+            _ => println!("Unknown command"),
+        }
+    }
+}
+
+fn replay(input: &str, part: &str) {
+    let (grid, robot, moves) = parse(input);
+    match part {
+        "1" => run_replay(&mut Replay::new(
+            grid,
+            robot,
+            moves,
+            apply_move,
+            render_grid,
+        )),
+        "2" => {
+            let grid = widen_grid(&grid);
+            let robot = find_robot_widen(&grid);
+            run_replay(&mut Replay::new(
+                grid,
+                robot,
+                moves,
+                apply_move_widen,
+                render_widen,
+            ));
         }
-        println!();
+        invalid => println!("Invalid replay part '{invalid}', expected 1 or 2"),
     }
 }
 
@@ -373,6 +587,13 @@ fn part_2(input: &'static str) {
 
 pub fn run() {
     let input = crate::utls::read_text_from_file("24", "15").leak();
+
+    // Replay the moves of the given part with `--replay <1|2>` instead of solving.
+    if let Some(part) = find_arg_value(env::args().skip(1), "--replay") {
+        replay(input, &part);
+        return;
+    }
+
     part_1(input);
     part_2(input);
 }
@@ -425,4 +646,51 @@ v^^>>><<^^<>>^v^<v^vv<>v^<<>^<^v^v><^<<<><<^<v><v<>vv>>v><v^<vv<>v^<<^";
         let sum_widen = sum_gps_widen(INPUT);
         assert_eq!(sum_widen, 9021);
     }
+
+    #[test]
+    fn test_replay() {
+        let (grid, robot, moves) = parse(INPUT_2);
+        let mut replay = Replay::new(grid, robot, moves, apply_move, render_grid);
+
+        // The fourth move pushes one box and the fifth pushes it into another one.
+        replay.jump(5);
+        assert_eq!(replay.pushed, vec![Pos::new(1, 5), Pos::new(1, 6)]);
+        let after_5 = replay.grid.clone();
+
+        assert!(replay.back());
+        assert_eq!(replay.applied, 4);
+        assert_eq!(replay.pushed, vec![Pos::new(1, 4)]);
+        assert!(replay.forward());
+        assert_eq!(replay.grid, after_5);
+
+        replay.jump(1);
+        assert!(replay.pushed.is_empty());
+
+        replay.jump(usize::MAX);
+        assert!(!replay.forward());
+        assert!(replay.frame().starts_with("Move 15/15: Left\n"));
+
+        // Going back over many checkpoints matches applying the moves directly.
+        let (grid, _, moves) = parse(INPUT);
+        let grid = widen_grid(&grid);
+        let robot = find_robot_widen(&grid);
+        let mut replay = Replay::new(
+            grid.clone(),
+            robot,
+            moves.clone(),
+            apply_move_widen,
+            render_widen,
+        );
+        replay.jump(moves.len());
+        replay.jump(250);
+        assert_eq!(replay.applied, 250);
+
+        let mut direct = grid;
+        let mut direct_robot = robot;
+        for &dir in &moves[..250] {
+            direct_robot = apply_move_widen(&mut direct, direct_robot, dir).0;
+        }
+        assert_eq!(replay.grid, direct);
+        assert_eq!(replay.robot, direct_robot);
+    }
 }
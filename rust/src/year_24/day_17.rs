@@ -1,4 +1,6 @@
-use std::ops::BitXor;
+use std::{env, ops::BitXor};
+
+use crate::utls::{find_arg_value, has_flag};
 
 #[derive(Debug, Clone)]
 struct Registers {
@@ -49,6 +51,16 @@ impl Operand {
             invaild => panic!("Invalid Opernad: {invaild}"),
         }
     }
+
+    fn combo_name(self) -> String {
+        match self.num {
+            n @ 0..=3 => n.to_string(),
+            4 => String::from("a"),
+            5 => String::from("b"),
+            6 => String::from("c"),
+            invaild => panic!("Invalid Opernad: {invaild}"),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -120,6 +132,86 @@ impl Opcode {
 
         idx + 2
     }
+
+    fn disassemble(self, operand: Operand) -> String {
+        match self {
+            Opcode::Adv => format!("a = a >> {}", operand.combo_name()),
+            Opcode::Bxl => format!("b = b ^ {}", operand.literal()),
+            Opcode::Bst => format!("b = {} % 8", operand.combo_name()),
+            Opcode::Jnz => format!("jnz {}", operand.literal()),
+            Opcode::Bxc => String::from("b = b ^ c"),
+            Opcode::Out => format!("out {}", operand.combo_name()),
+            Opcode::Bdv => format!("b = a >> {}", operand.combo_name()),
+            Opcode::Cdv => format!("c = a >> {}", operand.combo_name()),
+        }
+    }
+}
+
+fn instructions(nums: &[u8]) -> impl Iterator<Item = (usize, Opcode, Operand)> + '_ {
+    nums.chunks_exact(2)
+        .enumerate()
+        .map(|(idx, pair)| (idx * 2, Opcode::from(pair[0]), Operand::from(pair[1])))
+}
+
+/// Returns the program as readable operations, one line per instruction.
+fn disassemble(nums: &[u8]) -> Vec<String> {
+    instructions(nums)
+        .map(|(idx, opcode, operand)| format!("{idx:>2}: {}", opcode.disassemble(operand)))
+        .collect()
+}
+
+fn print_program(input: &str) {
+    let (_, nums) = parse(input);
+    for line in disassemble(&nums) {
+        println!("{line}");
+    }
+}
+
+#[derive(Debug, Clone)]
+struct TraceStep {
+    idx: usize,
+    op: String,
+    regs: Registers,
+    out: Option<u8>,
+}
+
+/// Runs the program like `run_insts` while recording the registers after each instruction.
+/// Stops after [`STEPS_LIMIT`] instructions since the program may never halt.
+fn trace_insts(mut regs: Registers, nums: &[u8]) -> Vec<TraceStep> {
+    let mut idx = 0;
+
+    let mut out = Vec::new();
+    let mut steps = Vec::new();
+    while let Some(&opcode) = nums.get(idx)
+        && steps.len() < STEPS_LIMIT
+    {
+        let opcode = Opcode::from(opcode);
+        let operand = Operand::from(nums[idx + 1]);
+        let out_len = out.len();
+        let next_idx = opcode.apply(idx, operand, &mut regs, &mut out);
+        steps.push(TraceStep {
+            idx,
+            op: opcode.disassemble(operand),
+            regs: regs.clone(),
+            out: (out.len() > out_len).then(|| out[out_len]),
+        });
+        idx = next_idx;
+    }
+
+    steps
+}
+
+fn print_trace(input: &str, a: u64) {
+    let (mut regs, nums) = parse(input);
+    regs.a = a;
+    for step in trace_insts(regs, &nums) {
+        let Registers { a, b, c } = step.regs;
+        print!("{:>2}: {:<12} a: {a}, b: {b}, c: {c}", step.idx, step.op);
+        match step.out {
+            Some(val) => println!(", out: {val}"),
+            None => println!(),
+        }
+    }
 }
 
 fn parse(input: &str) -> (Registers, Vec<u8>) {
@@ -153,75 +245,157 @@ fn run_insts(mut regs: Registers, nums: &[u8]) -> Vec<u8> {
     out
 }
 
+/// Instructions after which a program is considered running forever.
+const STEPS_LIMIT: usize = 1 << 16;
+
+/// Checks if the program outputs itself, stopping as soon as the output diverges from it or the
+/// steps run out.
+fn outputs_itself(mut regs: Registers, nums: &[u8]) -> bool {
+    let mut idx = 0;
+
+    let mut out = Vec::new();
+    for _ in 0..STEPS_LIMIT {
+        let Some(&opcode) = nums.get(idx) else {
+            return out == nums;
+        };
+        let opcode = Opcode::from(opcode);
+        let operand = Operand::from(nums[idx + 1]);
+        idx = opcode.apply(idx, operand, &mut regs, &mut out);
+        if !nums.starts_with(&out) {
+            return false;
+        }
+    }
+
+    false
+}
+
 fn part_1(input: &'static str) {
     let out = calc_output(input);
     println!("Part 1 answer is '{out}'");
 }
 
-#[allow(unused)]
-fn print_pattern(input: &str, start: u64) {
-    let (mut regs, nums) = parse(input);
+/// Checks if registers B and C are written in each round before they are read, so a round
+/// depends on register A only.
+fn resets_b_and_c(insts: &[(usize, Opcode, Operand)]) -> bool {
+    let (mut b_set, mut c_set) = (false, false);
+    for &(_, opcode, operand) in insts {
+        let combo_reads = |reg| {
+            !matches!(opcode, Opcode::Bxl | Opcode::Jnz | Opcode::Bxc) && operand.literal() == reg
+        };
+        let reads_b = combo_reads(5) || matches!(opcode, Opcode::Bxl | Opcode::Bxc);
+        let reads_c = combo_reads(6) || matches!(opcode, Opcode::Bxc);
+        if (reads_b && !b_set) || (reads_c && !c_set) {
+            return false;
+        }
 
-    regs.a = start;
-    let out = run_insts(regs, &nums);
-    println!("x: {start}, out: {out:?}");
+        match opcode {
+            Opcode::Bst | Opcode::Bxl | Opcode::Bxc | Opcode::Bdv => b_set = true,
+            Opcode::Cdv => c_set = true,
+            _ => {}
+        }
+    }
+
+    true
 }
 
-fn find_reg_a(input: &str) -> u64 {
-    let (regs, nums) = parse(input);
+/// Checks if the program is a single loop which consumes three bits of register A in each round,
+/// outputs one value per round and jumps back to the start while A isn't zero. Registers B and
+/// C must be reset in each round for the rounds to be independent.
+fn is_shift_loop(nums: &[u8]) -> bool {
+    if !nums.len().is_multiple_of(2) || nums.len() < 4 {
+        return false;
+    }
 
-    // Note: Idea is taken from other solutions online.
-    // The digits in the output seem to change after a fixed period. Each
-    // period is the previous one times 8: [1, 8, 64, 512, ...].
-    //
-    // output:
-    //
-    // x: 0, out: [0]
-    // x: 7, out: [0]
-    // x: 8, out: [1, 0]
-    // x: 63, out: [7, 0]
-    // x: 64, out: [0, 1, 0]
-    // x: 511, out: [7, 7, 0]
-    // x: 512, out: [0, 0, 1, 0]
-
-    // Try all factors of each period and compare their output.
-
-    let mut factors = vec![0; nums.len()];
-
-    loop {
-        let mut init_a = 0;
-        for (idx, fact) in factors.iter().enumerate() {
-            init_a += 8u64.pow(idx as u32) * fact
-        }
+    let insts: Vec<_> = instructions(nums).collect();
 
-        let mut regs_clone = regs.clone();
-        regs_clone.a = init_a;
-        let out = run_insts(regs_clone, &nums);
-        if out == nums {
-            return init_a;
-        }
+    let (_, last_op, last_operand) = insts[insts.len() - 1];
+    if !matches!(last_op, Opcode::Jnz) || last_operand.literal() != 0 {
+        return false;
+    }
 
-        for i in (0..nums.len()).rev() {
-            if out.len() < i {
-                factors[i] += 1;
-                break;
-            }
+    let count = |pred: fn(Opcode, Operand) -> bool| {
+        insts
+            .iter()
+            .filter(|(_, opcode, operand)| pred(*opcode, *operand))
+            .count()
+    };
+
+    count(|op, _| matches!(op, Opcode::Jnz)) == 1
+        && count(|op, _| matches!(op, Opcode::Out)) == 1
+        && count(|op, _| matches!(op, Opcode::Adv)) == 1
+        && count(|op, operand| matches!(op, Opcode::Adv) && operand.literal() == 3) == 1
+        && resets_b_and_c(&insts)
+}
 
-            if out[i] != nums[i] {
-                factors[i] += 1;
-                break;
-            }
-        }
+/// Builds register A from the last output backwards. Each round only sees the next three bits of
+/// A, so the digits matching the tail of the program can be extended one octal digit at a time.
+fn reverse_search(regs: &Registers, nums: &[u8]) -> Option<u64> {
+    let mut candidates = vec![0];
+
+    for tail_start in (0..nums.len()).rev() {
+        let tail = &nums[tail_start..];
+        candidates = candidates
+            .into_iter()
+            .flat_map(|prefix: u64| (0..8).map(move |digit| (prefix << 3) | digit))
+            .filter(|&a| {
+                let mut regs = regs.clone();
+                regs.a = a;
+                run_insts(regs, nums) == tail
+            })
+            .collect();
     }
+
+    candidates.into_iter().min()
+}
+
+fn brute_search(regs: &Registers, nums: &[u8], limit: u64) -> Option<u64> {
+    (0..limit).find(|&a| {
+        let mut regs = regs.clone();
+        regs.a = a;
+        outputs_itself(regs, nums)
+    })
+}
+
+const BRUTE_LIMIT: u64 = 1 << 24;
+
+/// Finds the lowest value of register A making the program output itself.
+fn find_reg_a(input: &str) -> Option<u64> {
+    let (regs, nums) = parse(input);
+
+    if is_shift_loop(&nums)
+        && let Some(a) = reverse_search(&regs, &nums)
+    {
+        return Some(a);
+    }
+
+    brute_search(&regs, &nums, BRUTE_LIMIT)
 }
 
 fn part_2(input: &'static str) {
-    let ans = find_reg_a(input);
+    let ans = find_reg_a(input).expect("No quine found for register A");
     println!("Part 2 answer is {ans}");
 }
 
 pub fn run() {
     let input = crate::utls::read_text_from_file("24", "17").leak();
+
+    // Print the program with `--disassemble` or its run for register A with `--trace <a>`
+    // instead of solving.
+    let disassemble = has_flag(env::args().skip(1), "--disassemble");
+    let trace = find_arg_value(env::args().skip(1), "--trace");
+    if disassemble {
+        print_program(input);
+    }
+    if let Some(a) = &trace {
+        print_trace(
+            input,
+            a.parse().expect("--trace needs a value for register A"),
+        );
+    }
+    if disassemble || trace.is_some() {
+        return;
+    }
+
     part_1(input);
     part_2(input);
 }
@@ -230,6 +404,14 @@ pub fn run() {
 mod test {
     use super::*;
 
+    fn print_pattern(input: &str, start: u64) {
+        let (mut regs, nums) = parse(input);
+
+        regs.a = start;
+        let out = run_insts(regs, &nums);
+        println!("x: {start}, out: {out:?}");
+    }
+
     const INPUT: &str = "\
 Register A: 729
 Register B: 0
@@ -250,7 +432,7 @@ Program: 0,3,5,4,3,0";
         assert_eq!(out, "4,6,3,5,6,3,5,2,1,0");
 
         let reg = find_reg_a(INPUT_2);
-        assert_eq!(reg, 117440);
+        assert_eq!(reg, Some(117440));
 
         print_pattern(INPUT_2, 0);
         print_pattern(INPUT_2, 7);
@@ -260,4 +442,37 @@ Program: 0,3,5,4,3,0";
         print_pattern(INPUT_2, 511);
         print_pattern(INPUT_2, 512);
     }
+
+    #[test]
+    fn test_toolkit() {
+        let (regs, nums) = parse(INPUT_2);
+        assert_eq!(
+            disassemble(&nums),
+            vec![" 0: a = a >> 3", " 2: out a", " 4: jnz 0"]
+        );
+        assert!(is_shift_loop(&nums));
+        assert_eq!(brute_search(&regs, &nums, 200_000), Some(117440));
+
+        let (_, nums) = parse(INPUT);
+        assert!(!is_shift_loop(&nums));
+
+        // B carries over from the previous round.
+        assert!(!is_shift_loop(&[0, 3, 1, 1, 5, 5, 3, 0]));
+        assert!(is_shift_loop(&[2, 4, 1, 1, 7, 5, 0, 3, 4, 0, 5, 5, 3, 0]));
+
+        // Programs which never halt are given up instead of hanging the search.
+        let regs = Registers { a: 1, b: 0, c: 0 };
+        assert!(!outputs_itself(regs.clone(), &[3, 0]));
+        assert!(!outputs_itself(regs.clone(), &[5, 3, 3, 0]));
+        assert_eq!(brute_search(&regs, &[3, 0], 100), None);
+
+        let (mut regs, nums) = parse(INPUT_2);
+        regs.a = 8;
+        let trace = trace_insts(regs, &nums);
+        assert_eq!(trace.len(), 6);
+        assert_eq!(trace[0].regs.a, 1);
+        assert_eq!(trace[1].out, Some(1));
+        assert_eq!(trace[4].out, Some(0));
+        assert_eq!(trace[5].idx, 4);
+    }
 }
@@ -1,4 +1,11 @@
-use std::{collections::BTreeMap, fmt::Display};
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    fmt::Display,
+};
+
+use rand::{Rng, SeedableRng, rngs::StdRng};
+
+use crate::dot::{self, Graph, Shape};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum OpType {
@@ -102,25 +109,201 @@ fn part_1(input: &'static str) {
     println!("Part 1 answer is {ans}");
 }
 
-/// Prints the relations between the operations in dot format to visualize it with Graphviz
-fn print_graph(input: &str) {
-    let (_, ops) = parse(input);
-    println!("digraph {{");
-    println!("node [style=filled, shape=ellipse];");
-    // Mark starts and ends with special colors
-    // We have 44 nodes in input.
-    for i in 0..=44 {
-        println!("x{i:02} [fillcolor=blue, fontcolor=white]");
-        println!("y{i:02} [fillcolor=blue, fontcolor=white]");
-        println!("z{i:02} [fillcolor=orange]");
+/// Builds the graph of the circuit with the wires connected through the gates, where inputs,
+/// outputs and gate types have their own shapes.
+fn build_graph(ops: &[Operation]) -> Graph {
+    let mut graph = Graph::directed();
+
+    let wires: BTreeSet<&str> = ops
+        .iter()
+        .flat_map(|op| [&op.input1, &op.input2, &op.output])
+        .map(String::as_str)
+        .collect();
+    for wire in wires {
+        let shape = match wire {
+            _ if is_input_wire(wire) => Shape::InvTriangle,
+            _ if wire.starts_with('z') => Shape::DoubleCircle,
+            _ => Shape::Circle,
+        };
+        graph.node(wire, shape);
     }
-    // Print relations between operations in dot formats
+
     for op in ops {
-        println!("{} -> {}_{}_{};", op.input1, op.input1, op.otype, op.input2);
-        println!("{} -> {}_{}_{};", op.input2, op.input1, op.otype, op.input2);
-        println!("{}_{}_{} -> {};", op.input1, op.otype, op.input2, op.output);
+        let gate = format!("{}_{}_{}", op.input1, op.otype, op.input2);
+        let shape = match op.otype {
+            OpType::And => Shape::Box,
+            OpType::Or => Shape::Hexagon,
+            OpType::Xor => Shape::Diamond,
+        };
+        graph.labelled_node(&gate, op.otype, shape);
+        graph.edge(&op.input1, &gate);
+        graph.edge(&op.input2, &gate);
+        graph.edge(&gate, &op.output);
     }
-    println!("}}");
+
+    graph
+}
+
+fn is_input_wire(name: &str) -> bool {
+    name.starts_with('x') || name.starts_with('y')
+}
+
+/// Checks each gate against the rules of the ripple-carry full adder and returns the sorted
+/// outputs of the gates breaking them:
+///
+/// - Every Zn is the output of a XOR gate, except the last one which is the final carry (OR).
+/// - XOR gates which don't take Xn and Yn inputs are the sum gates and must output Zn.
+/// - `Xn XOR Yn` (Intermediate_sum) must feed into another XOR gate, except for the first bit.
+/// - AND gates (carries) must feed into an OR gate, except for the first bit which is the carry.
+fn find_miswired(ops: &[Operation]) -> Vec<String> {
+    let last_z = ops
+        .iter()
+        .map(|op| op.output.as_str())
+        .filter(|output| output.starts_with('z'))
+        .max()
+        .unwrap();
+
+    let feeds_into = |output: &str, otype: OpType| {
+        ops.iter().any(|op| {
+            op.otype == otype && (op.input1.as_str() == output || op.input2.as_str() == output)
+        })
+    };
+
+    let mut wrong: Vec<_> = ops
+        .iter()
+        .filter(|op| {
+            let first_bit = op.input1 == "x00" || op.input2 == "x00";
+            let inputs_xy = is_input_wire(&op.input1) && is_input_wire(&op.input2);
+            let output_z = op.output.starts_with('z');
+
+            match op.otype {
+                _ if op.output == last_z => op.otype != OpType::Or,
+                OpType::Xor if inputs_xy => {
+                    if first_bit {
+                        op.output != "z00"
+                    } else {
+                        !feeds_into(&op.output, OpType::Xor)
+                    }
+                }
+                OpType::Xor => !output_z,
+                OpType::And if first_bit => output_z,
+                OpType::And => !feeds_into(&op.output, OpType::Or),
+                OpType::Or => output_z,
+            }
+        })
+        .map(|op| op.output.clone())
+        .collect();
+
+    wrong.sort_unstable();
+    wrong.dedup();
+
+    wrong
+}
+
+/// Runs the circuit on the given numbers. Returns None if the gates depend on each other.
+fn simulate(ops: &[Operation], bits: usize, x: u64, y: u64) -> Option<u64> {
+    let mut values: HashMap<&str, u8> = HashMap::new();
+    let names: Vec<_> = (0..bits)
+        .flat_map(|idx| [format!("x{idx:02}"), format!("y{idx:02}")])
+        .collect();
+    for (idx, name) in names.iter().enumerate() {
+        let num = if idx % 2 == 0 { x } else { y };
+        values.insert(name, (num >> (idx / 2) & 1) as u8);
+    }
+
+    let mut pending: Vec<_> = ops.iter().collect();
+    while !pending.is_empty() {
+        let before = pending.len();
+        pending.retain(|op| {
+            let (Some(n1), Some(n2)) = (
+                values.get(op.input1.as_str()),
+                values.get(op.input2.as_str()),
+            ) else {
+                return true;
+            };
+            let res = op.otype.apply(*n1, *n2);
+            values.insert(&op.output, res);
+            false
+        });
+        if pending.len() == before {
+            return None;
+        }
+    }
+
+    let z = values
+        .iter()
+        .filter(|(name, _)| name.starts_with('z'))
+        .fold(0, |acc, (name, &val)| {
+            let idx: u32 = name[1..].parse().unwrap();
+            acc | (val as u64) << idx
+        });
+
+    Some(z)
+}
+
+fn apply_swaps(ops: &[Operation], swaps: &[(String, String)]) -> Vec<Operation> {
+    let mut ops = ops.to_vec();
+    for op in ops.iter_mut() {
+        for (first, second) in swaps {
+            if op.output == *first {
+                op.output = second.clone();
+            } else if op.output == *second {
+                op.output = first.clone();
+            }
+        }
+    }
+
+    ops
+}
+
+/// Seed of the random additions, so a circuit is always checked with the same cases.
+const SAMPLES_SEED: u64 = 24;
+
+/// Checks the circuit with random additions together with the ones running through all carries.
+fn is_valid_adder(ops: &[Operation], bits: usize, rounds: usize) -> bool {
+    let max = (1 << bits) - 1;
+    let mut rng = StdRng::seed_from_u64(SAMPLES_SEED);
+    let mut cases = vec![(0, 0), (max, 1), (1, max), (max, max)];
+    cases.extend((0..rounds).map(|_| (rng.random_range(0..=max), rng.random_range(0..=max))));
+
+    cases
+        .into_iter()
+        .all(|(x, y)| simulate(ops, bits, x, y) == Some(x + y))
+}
+
+/// Tries all the ways to pair the wrong wires and returns the swaps which fix the adder.
+fn find_swaps(ops: &[Operation], bits: usize, wires: &[String]) -> Option<Vec<(String, String)>> {
+    fn pairings(wires: &[String]) -> Vec<Vec<(String, String)>> {
+        let Some((first, rest)) = wires.split_first() else {
+            return vec![Vec::new()];
+        };
+
+        let mut all = Vec::new();
+        for idx in 0..rest.len() {
+            let mut remaining = rest.to_vec();
+            let second = remaining.remove(idx);
+            for mut pairs in pairings(&remaining) {
+                pairs.push((first.clone(), second.clone()));
+                all.push(pairs);
+            }
+        }
+
+        all
+    }
+
+    pairings(wires)
+        .into_iter()
+        .find(|swaps| is_valid_adder(&apply_swaps(ops, swaps), bits, 100))
+}
+
+fn calc_swapped_wires(input: &str) -> String {
+    let (known, ops) = parse(input);
+    let bits = known.keys().filter(|name| name.starts_with('x')).count();
+
+    let wires = find_miswired(&ops);
+    find_swaps(&ops, bits, &wires).expect("swapping the wrong wires doesn't fix the adder");
+
+    wires.join(",")
 }
 
 fn part_2(input: &'static str) {
@@ -135,32 +318,13 @@ fn part_2(input: &'static str) {
     // - prev_input_carry XOR Intermediate_sum -> Zn (sum result)
     // - carry_of_intermediate_sum OR Intermediate_carry -> final_carry
     //
-    // *********************************************************
-    //
-    // I ended up printing the nodes and inspecting it manually for displaced outputs,
-    // Then I wrote them here, sorted them then joined them for both inputs: (The one for my
-    // personal account and then one for ESR event)
-
-    print_graph(input);
-
-    // **********************************
-    // *** Output for ESR group input ***
-    // **********************************
-    let mut esr_input_swap = ["bpf", "z05", "hcc", "z11", "hqc", "qcw", "z35", "fdw"];
-    esr_input_swap.sort_unstable();
-
-    let ans_esr: String = esr_input_swap.join(",");
+    // The gates breaking these rules are found in `find_miswired` then the fix is checked by
+    // simulating additions with the swapped outputs.
 
-    println!("Part 2 ESR input is '{ans_esr}'");
+    let ans = calc_swapped_wires(input);
+    println!("Part 2 answer is '{ans}'");
 
-    // *******************************************
-    // *** Output for my personal account input ***
-    // *******************************************
-    let mut my_input_swap = ["cmv", "z17", "rmj", "z23", "rdg", "z30", "mwp", "btb"];
-    my_input_swap.sort_unstable();
-    let ans_my = my_input_swap.join(",");
-
-    println!("Part 2 personal input is '{ans_my}'");
+    dot::export("24_24_circuit", || build_graph(&parse(input).1));
 }
 
 pub fn run() {
@@ -227,4 +391,63 @@ tnw OR pbm -> gnj";
         let ans = calc_outputz_sum(INPUT);
         assert_eq!(ans, 2024);
     }
+
+    /// Creates a ripple-carry adder input with the given swapped outputs.
+    fn build_adder(bits: usize, swaps: &[(&str, &str)]) -> String {
+        let mut gates = vec![
+            (String::from("x00 XOR y00"), String::from("z00")),
+            (String::from("x00 AND y00"), String::from("c00")),
+        ];
+        for idx in 1..bits {
+            let carry = if idx == bits - 1 {
+                format!("z{bits:02}")
+            } else {
+                format!("c{idx:02}")
+            };
+            let prev = idx - 1;
+            gates.extend([
+                (format!("x{idx:02} XOR y{idx:02}"), format!("s{idx:02}")),
+                (format!("x{idx:02} AND y{idx:02}"), format!("a{idx:02}")),
+                (format!("c{prev:02} XOR s{idx:02}"), format!("z{idx:02}")),
+                (format!("c{prev:02} AND s{idx:02}"), format!("b{idx:02}")),
+                (format!("b{idx:02} OR a{idx:02}"), carry),
+            ]);
+        }
+
+        let known = (0..bits).flat_map(|idx| [format!("x{idx:02}: 0"), format!("y{idx:02}: 0")]);
+        let ops = gates.into_iter().map(|(expr, output)| {
+            let output = swaps
+                .iter()
+                .find_map(|&(first, second)| match output.as_str() {
+                    out if out == first => Some(second.to_string()),
+                    out if out == second => Some(first.to_string()),
+                    _ => None,
+                })
+                .unwrap_or(output);
+            format!("{expr} -> {output}")
+        });
+
+        let known: Vec<_> = known.collect();
+        let ops: Vec<_> = ops.collect();
+        format!("{}\n\n{}", known.join("\n"), ops.join("\n"))
+    }
+
+    #[test]
+    fn test_miswired() {
+        let input = build_adder(8, &[]);
+        let (_, ops) = parse(&input);
+        assert!(find_miswired(&ops).is_empty());
+        assert!(is_valid_adder(&ops, 8, 20));
+
+        let input = build_adder(8, &[("s02", "a02"), ("z05", "b05")]);
+        let (_, ops) = parse(&input);
+        assert_eq!(find_miswired(&ops), ["a02", "b05", "s02", "z05"]);
+        assert!(!is_valid_adder(&ops, 8, 20));
+
+        let swaps = find_swaps(&ops, 8, &find_miswired(&ops)).unwrap();
+        assert_eq!(swaps.len(), 2);
+        assert!(is_valid_adder(&apply_swaps(&ops, &swaps), 8, 20));
+
+        assert_eq!(calc_swapped_wires(&input), "a02,b05,s02,z05");
+    }
 }